@@ -0,0 +1,4673 @@
+use bevy::{
+    ecs::system::SystemParam,
+    input::{keyboard::KeyboardInput, mouse::MouseButtonInput, ButtonState, InputSystem},
+    prelude::*,
+    window::{CursorMoved, PrimaryWindow},
+};
+use bevy_cosmic_edit::*;
+use tree_builder::{node, EntityTreeExt};
+
+pub use binding::AppBindExt;
+pub use derived::{AppDeriveExt, Derive};
+pub use l10n::Locale;
+pub use recording::Recording;
+pub use routing::Route;
+pub use theme::{Palette, Spacing, TextStyles, Theme, ThemeText};
+
+/// The todo app: models, views and the systems binding them
+///
+/// ```no_run
+/// use bevy::prelude::*;
+/// use bevy_todomvc::TodoMvcPlugin;
+///
+/// App::new()
+///     .add_plugins(DefaultPlugins)
+///     .add_plugins(TodoMvcPlugin::default())
+///     .run();
+/// ```
+#[derive(Clone)]
+pub struct TodoMvcPlugin {
+    /// UI node to spawn the app into,
+    /// `None` spawns a window-filling root node together with a 2d camera
+    pub parent: Option<Entity>,
+    pub fonts: TodoMvcFonts,
+    pub features: TodoMvcFeatures,
+    pub recording: TodoMvcRecording,
+    /// Asset path of a `.theme.ron` file, reapplied whenever the asset is reloaded,
+    /// `None` keeps the [`Theme`] resource as inserted, or the light theme
+    pub theme: Option<String>,
+}
+
+impl Default for TodoMvcPlugin {
+    fn default() -> Self {
+        Self {
+            parent: None,
+            fonts: default(),
+            features: default(),
+            recording: default(),
+            theme: None,
+        }
+    }
+}
+
+/// Asset paths of the fonts used by the app's `Text`s
+#[derive(Clone)]
+pub struct TodoMvcFonts {
+    /// Labels, titles and buttons, `None` is bevy's default font
+    pub text: Option<String>,
+    /// Must contain the Material Icons `check_circle` glyphs
+    pub icons: String,
+}
+
+impl Default for TodoMvcFonts {
+    fn default() -> Self {
+        Self {
+            text: None,
+            icons: "fonts/MaterialIcons-Regular-subset.ttf".to_string(),
+        }
+    }
+}
+
+/// Optional parts of the app, all enabled by default
+#[derive(Clone, Copy)]
+pub struct TodoMvcFeatures {
+    /// Load todos on startup and save them on every change
+    pub persistence: bool,
+    /// Ctrl+Z / Ctrl+Shift+Z
+    pub undo: bool,
+    /// Sync the filter with `location.hash` on wasm and `--route` natively
+    pub routing: bool,
+}
+
+impl Default for TodoMvcFeatures {
+    fn default() -> Self {
+        Self {
+            persistence: true,
+            undo: true,
+            routing: true,
+        }
+    }
+}
+
+/// Input handling can be recorded to a file and replayed (e.g. in a headless test) from one
+#[derive(Clone, Default)]
+pub enum TodoMvcRecording {
+    #[default]
+    Off,
+    /// Writes to this file, overwriting it
+    Record(std::path::PathBuf),
+    /// Feeds the recording back instead of live input until it ends,
+    /// starting from its todos instead of the stored ones, which are left untouched
+    Replay(Recording),
+}
+
+/// The systems turning `Interaction`s, keys and editor changes into model actions
+///
+/// Off while a recording is replayed.
+#[derive(SystemSet, Clone, PartialEq, Eq, Hash, Debug)]
+pub struct TodoMvcInput;
+
+/// The systems applying actions to models
+#[derive(SystemSet, Clone, PartialEq, Eq, Hash, Debug)]
+pub struct TodoMvcModels;
+
+/// The systems updating derived models from models, see [`AppDeriveExt::derive`]
+#[derive(SystemSet, Clone, PartialEq, Eq, Hash, Debug)]
+pub struct TodoMvcDerivedModels;
+
+/// The systems updating views from their models, see [`AppBindExt::bind`]
+#[derive(SystemSet, Clone, PartialEq, Eq, Hash, Debug)]
+pub struct TodoMvcViews;
+
+/// The models, input handling and views of [`TodoMvcPlugin`],
+/// without fonts, localisation, a camera or cosmic-edit's rendering
+///
+/// Runs with `MinimalPlugins` alone, as in the headless tests:
+/// `Input<KeyCode>` must be inserted and `Interaction`s are set by hand.
+#[derive(Clone, Default)]
+pub struct TodoMvcCorePlugin {
+    /// See [`TodoMvcPlugin::parent`]
+    pub parent: Option<Entity>,
+    pub features: TodoMvcFeatures,
+    pub recording: TodoMvcRecording,
+}
+
+/// The plugin's settings, as seen by its systems
+#[derive(Resource, Clone)]
+struct TodoMvcConfig {
+    parent: Option<Entity>,
+}
+
+/// Font handles for the app's `Text`s
+///
+/// Loaded from [`TodoMvcFonts`] unless it is already inserted.
+#[derive(Resource, Clone, Default)]
+pub struct Fonts {
+    pub text: Handle<Font>,
+    pub icons: Handle<Font>,
+}
+
+impl Plugin for TodoMvcPlugin {
+    fn build(&self, app: &mut App) {
+        if !app.is_plugin_added::<CosmicEditPlugin>() {
+            app.add_plugins(CosmicEditPlugin::default());
+        }
+        if !app.world.contains_resource::<Fonts>() {
+            let asset_server = app.world.resource::<AssetServer>();
+            let fonts = Fonts {
+                text: self
+                    .fonts
+                    .text
+                    .as_ref()
+                    .map(|path| asset_server.load(path.as_str()))
+                    .unwrap_or_default(),
+                icons: asset_server.load(self.fonts.icons.as_str()),
+            };
+            app.insert_resource(fonts);
+        }
+        app.add_plugins(TodoMvcCorePlugin {
+            parent: self.parent,
+            features: self.features,
+            recording: self.recording.clone(),
+        })
+        .init_resource::<l10n::Locale>()
+        .init_resource::<l10n::MessageCatalogues>()
+        .add_asset::<l10n::MessageCatalogue>()
+        .init_asset_loader::<l10n::MessageCatalogueLoader>()
+        .add_asset::<theme::ThemeAsset>()
+        .init_asset_loader::<theme::ThemeLoader>()
+        .add_systems(Update, l10n::load_catalogues)
+        .add_systems(Update, update_localized_text.after(TodoMvcViews))
+        .add_systems(
+            Update,
+            update_localized_placeholder.after(display_text_input),
+        );
+
+        if let Some(path) = &self.theme {
+            let handle = app.world.resource::<AssetServer>().load(path.as_str());
+            app.insert_resource(theme::ThemeSource(handle))
+                .add_systems(Update, theme::apply_theme_asset.before(TodoMvcViews));
+        }
+        if self.parent.is_none() {
+            app.add_systems(Startup, setup)
+                .add_systems(Update, update_localized_window_title);
+        }
+        #[cfg(target_arch = "wasm32")]
+        if self.features.routing {
+            app.add_systems(Startup, routing::listen_hash_changes)
+                .add_systems(PreUpdate, routing::handle_hash_change)
+                .add_systems(PostUpdate, routing::update_location_hash);
+        }
+    }
+}
+
+impl Plugin for TodoMvcCorePlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(TodoMvcConfig {
+            parent: self.parent,
+        })
+        .init_resource::<Fonts>()
+        .init_resource::<Theme>()
+        .add_event::<CosmicTextChanged>()
+        .add_event::<ModelTodoAction>()
+        .add_event::<ModelInputAction>()
+        .add_event::<ModelFilterAction>()
+        .add_event::<HistoryAction>()
+        .add_event::<SetFocus>()
+        .add_event::<ModelActionError>()
+        .init_resource::<Focus>()
+        .init_resource::<History>()
+        .init_resource::<TodoIndex>()
+        .init_resource::<ClickTracker>()
+        .add_systems(Startup, setup_ui)
+        .configure_set(
+            PreUpdate,
+            TodoMvcInput
+                .after(InputSystem)
+                .before(handle_focus)
+                .run_if(live_input),
+        )
+        .add_systems(PreUpdate, handle_deleter_interaction.in_set(TodoMvcInput))
+        .add_systems(PreUpdate, handle_checkmark_interaction.in_set(TodoMvcInput))
+        .add_systems(PreUpdate, track_clicks.in_set(TodoMvcInput))
+        .add_systems(
+            PreUpdate,
+            handle_text_interaction
+                .after(track_clicks)
+                .in_set(TodoMvcInput),
+        )
+        .add_systems(PreUpdate, handle_input_interaction.in_set(TodoMvcInput))
+        .add_systems(PreUpdate, handle_filter_interaction.in_set(TodoMvcInput))
+        .add_systems(
+            PreUpdate,
+            handle_clear_completed_interaction.in_set(TodoMvcInput),
+        )
+        .add_systems(
+            PreUpdate,
+            handle_toggle_all_interaction.in_set(TodoMvcInput),
+        )
+        .add_systems(PreUpdate, handle_enter.in_set(TodoMvcInput))
+        .add_systems(PreUpdate, handle_escape.in_set(TodoMvcInput))
+        .add_systems(PreUpdate, handle_cosmic_change.in_set(TodoMvcInput))
+        .add_systems(PreUpdate, handle_focus)
+        .configure_set(Update, TodoMvcDerivedModels.after(TodoMvcModels))
+        .configure_set(Update, TodoMvcViews.after(TodoMvcDerivedModels))
+        .add_systems(Update, update_todo_model.in_set(TodoMvcModels))
+        .add_systems(Update, update_input_model.in_set(TodoMvcModels))
+        .add_systems(
+            Update,
+            update_filter_model
+                .after(update_filter_from_route)
+                .in_set(TodoMvcModels),
+        )
+        .derive::<TodoActiveCount>()
+        .derive::<TodoCompletedCount>()
+        .derive::<TodoTotalCount>()
+        .derive::<TodoAllChecked>()
+        .add_systems(Update, display_todos.after(update_todo_model))
+        .add_systems(Update, display_text_input.after(update_input_model))
+        .bind::<ModelTodoText, CosmicText, markers::TodoTextView>(|text, view| {
+            *view = CosmicText::OneStyle(text.0.clone());
+        })
+        .add_systems(Update, update_todo_text_color.in_set(TodoMvcViews))
+        .bind_themed::<ModelTodoChecked, Text, markers::TodoCheckmarkView>(
+            |checked, text, theme| {
+                let font = text.sections[0].style.font.clone();
+                text.sections[0].value = display_checked_text(checked);
+                text.sections[0].style = display_checked_style(theme, checked, font);
+            },
+        )
+        .bind::<ModelInputText, CosmicText, markers::TodoInput>(|text, view| {
+            *view = CosmicText::OneStyle(text.0.clone());
+        })
+        .bind::<DerivedModel<TodoActiveCount>, l10n::Localized, markers::TodoItemsLeftView>(
+            |count, localized| localized.count = Some(count.0 .0),
+        )
+        .add_systems(Update, restyle.in_set(TodoMvcViews))
+        .add_systems(Update, redraw_changed_attrs.after(TodoMvcViews))
+        .add_systems(
+            Update,
+            update_focus_main_input
+                .after(update_input_model)
+                .after(update_todo_model),
+        )
+        .add_systems(
+            Update,
+            update_focus_todo
+                .after(update_input_model)
+                .after(update_todo_model)
+                // so that focusing the main input wins over unfocusing a todo
+                .before(update_focus_main_input),
+        )
+        .add_systems(
+            Update,
+            update_displayed_filter_buttons.after(update_filter_model),
+        )
+        .add_systems(
+            Update,
+            update_displayed_todos_visibility
+                .after(update_filter_model)
+                .after(update_todo_model),
+        )
+        .add_systems(
+            Update,
+            update_displayed_clear_completed.in_set(TodoMvcViews),
+        )
+        .add_systems(
+            Update,
+            update_displayed_list_and_footer.in_set(TodoMvcViews),
+        )
+        .add_systems(Update, update_displayed_toggle_all.in_set(TodoMvcViews))
+        .add_systems(Update, update_show_on_hover)
+        .add_systems(Update, update_hover_text_color)
+        .add_systems(PostUpdate, remove_displayed_todos)
+        .add_systems(PostUpdate, log_model_action_errors);
+
+        match &self.recording {
+            TodoMvcRecording::Off => {}
+            TodoMvcRecording::Record(path) => {
+                app.insert_resource(recording::Recorder::create(path))
+                    .add_systems(PostStartup, write_recording_header)
+                    .add_systems(PreUpdate, skip_unrecorded_events.before(TodoMvcInput))
+                    .add_systems(
+                        PreUpdate,
+                        record_frame.after(TodoMvcInput).before(handle_focus),
+                    );
+            }
+            TodoMvcRecording::Replay(replayed) => {
+                app.insert_resource(recording::Replay::new(replayed.clone()))
+                    .add_systems(Startup, load_replayed_todos)
+                    .add_systems(
+                        PreUpdate,
+                        replay_frame.after(TodoMvcInput).before(handle_focus),
+                    );
+            }
+        }
+        let replaying = matches!(self.recording, TodoMvcRecording::Replay(_));
+        if self.features.persistence && !replaying {
+            app.add_systems(Startup, load_todos)
+                .add_systems(PostUpdate, save_todos);
+        }
+        if self.features.undo {
+            app.add_systems(PreUpdate, handle_undo.in_set(TodoMvcInput));
+        }
+        if self.features.routing {
+            app.init_resource::<routing::Route>()
+                .add_systems(Update, update_filter_from_route)
+                .add_systems(Update, update_route_from_filter.after(update_filter_model));
+        }
+    }
+}
+
+#[derive(Event)]
+struct SetFocus(Option<Entity>);
+
+/// Scale factor of the primary window, 1 without one (e.g. headless)
+fn scale_factor(windows: &Query<&Window, With<PrimaryWindow>>) -> f32 {
+    windows
+        .get_single()
+        .map_or(1., |window| window.scale_factor() as f32)
+}
+
+fn setup(mut commands: Commands) {
+    commands.spawn(Camera2dBundle::default());
+}
+
+fn setup_ui(
+    mut commands: Commands,
+    mut input_actions: EventWriter<ModelInputAction>,
+    config: Res<TodoMvcConfig>,
+    fonts: Res<Fonts>,
+    theme: Res<Theme>,
+) {
+    let todo_all_checked_model = commands
+        .spawn((DerivedModel(TodoAllChecked(false)), Model))
+        .id();
+    let todo_total_model = commands
+        .spawn((DerivedModel(TodoTotalCount(0)), Model))
+        .id();
+    let todo_count = 0;
+    let todo_items_left_model = commands
+        .spawn((DerivedModel(TodoActiveCount(todo_count)), Model))
+        .id();
+    let todo_filter_model = commands.spawn((ModelFilter(TodoFilter::All), Model)).id();
+    let todo_completed_model = commands
+        .spawn((DerivedModel(TodoCompletedCount(0)), Model))
+        .id();
+
+    let filter_btn = |border_color: Color| {
+        (
+            ButtonBundle {
+                border_color: border_color.into(),
+                background_color: theme.palette.todoapp_background.into(),
+                style: Style {
+                    border: UiRect::all(Val::Px(1.0)),
+                    padding: UiRect::axes(Val::Px(7.0), Val::Px(3.0)),
+                    margin: UiRect::all(Val::Px(3.0)),
+                    ..default()
+                },
+                ..default()
+            },
+            Styled::Button,
+        )
+    };
+    let footer_txt = |key: &'static str| {
+        (
+            TextBundle::from_section("", theme.text.footer.with_font(fonts.text.clone())),
+            l10n::Localized::new(key),
+            Styled::Footer,
+        )
+    };
+
+    let spawned = tree_builder::spawn(
+        &mut commands,
+        ui![
+            app_main: (
+                NodeBundle {
+                    style: Style {
+                        height: Val::Percent(100.),
+                        width: Val::Percent(100.),
+                        align_items: AlignItems::Center,
+                        flex_direction: FlexDirection::Column,
+                        ..default()
+                    },
+                    background_color: theme.palette.body_background.into(),
+                    ..default()
+                },
+                Styled::Page,
+            ) => [
+                app_title: (
+                    TextBundle::from_section("", theme.text.title.with_font(fonts.text.clone())),
+                    l10n::Localized::new("title"),
+                    Styled::Title,
+                ),
+                todo_main: (
+                    NodeBundle {
+                        style: Style {
+                            width: Val::Px(550.),
+                            align_items: AlignItems::Center,
+                            flex_direction: FlexDirection::Column,
+                            ..default()
+                        },
+                        background_color: theme.palette.todoapp_background.into(),
+                        ..default()
+                    },
+                    Styled::App,
+                ) => [
+                    todo_input_container: (
+                        NodeBundle {
+                            style: Style {
+                                justify_content: JustifyContent::Start,
+                                overflow: Overflow::clip(),
+                                align_items: AlignItems::Start,
+                                margin: UiRect::all(Val::Px(10.)),
+                                min_width: Val::Px(500.),
+                                height: Val::Px(40.),
+                                ..default()
+                            },
+                            ..default()
+                        },
+                        markers::TodoInputContainer,
+                    ) => [
+                        todo_toggle_all_btn: (
+                            ButtonBundle {
+                                style: Style {
+                                    width: Val::Px(40.),
+                                    height: Val::Px(40.),
+                                    justify_content: JustifyContent::Center,
+                                    align_items: AlignItems::Center,
+                                    flex_shrink: 0.,
+                                    ..default()
+                                },
+                                background_color: theme.palette.todoapp_background.into(),
+                                ..default()
+                            },
+                            markers::TodoToggleAll,
+                            View(todo_all_checked_model),
+                            Styled::Button,
+                        ) => [
+                            todo_toggle_all_txt: (
+                                TextBundle {
+                                    text: Text::from_section(
+                                        ">",
+                                        theme.text.toggle_all.with_font(fonts.text.clone()),
+                                    ),
+                                    // `>` turned downwards, like the `❯` rotated by 90deg in the TodoMVC CSS
+                                    transform: Transform::from_rotation(Quat::from_rotation_z(
+                                        -std::f32::consts::FRAC_PI_2,
+                                    )),
+                                    ..default()
+                                },
+                                markers::TodoToggleAll,
+                                View(todo_all_checked_model),
+                            ),
+                        ],
+                    ],
+                    todo_list: (
+                        NodeBundle {
+                            style: Style {
+                                display: Display::None,
+                                flex_direction: FlexDirection::Column,
+                                width: Val::Percent(100.0),
+                                border: UiRect::top(Val::Px(1.0)),
+                                ..default()
+                            },
+                            border_color: theme.palette.main_border_top.into(),
+                            ..default()
+                        },
+                        markers::TodoList,
+                        View(todo_total_model),
+                        Styled::List,
+                    ),
+                    todo_footer: (
+                        NodeBundle {
+                            style: Style {
+                                display: Display::None,
+                                flex_direction: FlexDirection::Row,
+                                justify_content: JustifyContent::SpaceBetween,
+                                align_items: AlignItems::Center,
+                                width: Val::Percent(100.),
+                                padding: UiRect::axes(Val::Px(15.0), Val::Px(10.0)),
+                                ..default()
+                            },
+                            ..default()
+                        },
+                        markers::TodoFooter,
+                        View(todo_total_model),
+                    ) => [
+                        todo_items_left: (
+                            TextBundle::from_section(
+                                "",
+                                theme.text.footer.with_font(fonts.text.clone()),
+                            ),
+                            l10n::Localized::plural("items-left", todo_count),
+                            markers::TodoItemsLeftView,
+                            View(todo_items_left_model),
+                            Styled::Footer,
+                        ),
+                        todo_filters: (
+                            NodeBundle {
+                                style: Style {
+                                    flex_direction: FlexDirection::Row,
+                                    ..default()
+                                },
+                                ..default()
+                            },
+                            markers::TodoFilters,
+                        ) => [
+                            todo_filter_all_btn: (
+                                filter_btn(theme.palette.filters_li_a_selected),
+                                markers::TodoFilterAll,
+                                View(todo_filter_model),
+                            ) => [
+                                todo_filter_all_txt: footer_txt("filter-all"),
+                            ],
+                            todo_filter_active_btn: (
+                                filter_btn(Color::NONE),
+                                markers::TodoFilterActive,
+                                View(todo_filter_model),
+                            ) => [
+                                todo_filter_active_txt: footer_txt("filter-active"),
+                            ],
+                            todo_filter_completed_btn: (
+                                filter_btn(Color::NONE),
+                                markers::TodoFilterCompleted,
+                                View(todo_filter_model),
+                            ) => [
+                                todo_filter_completed_txt: footer_txt("filter-completed"),
+                            ],
+                        ],
+                        todo_clear_completed_btn: (
+                            ButtonBundle {
+                                style: Style {
+                                    display: Display::None,
+                                    ..default()
+                                },
+                                background_color: theme.palette.todoapp_background.into(),
+                                ..default()
+                            },
+                            markers::TodoClearCompleted,
+                            View(todo_completed_model),
+                            Styled::Button,
+                        ) => [
+                            todo_clear_completed_txt: footer_txt("clear-completed"),
+                        ],
+                    ],
+                ],
+            ],
+        ],
+    );
+    if let Some(parent) = config.parent {
+        commands.entity(parent).add_child(spawned["app_main"]);
+    }
+
+    input_actions.send(ModelInputAction::Create("".to_string()));
+}
+
+/// Interaction -> Event<ModelTodoAction>
+#[allow(clippy::type_complexity)]
+fn handle_deleter_interaction(
+    mut delete_interaction_q: Query<
+        (&Interaction, &TodoId),
+        (Changed<Interaction>, With<markers::TodoDeleterView>),
+    >,
+    mut actions: EventWriter<ModelTodoAction>,
+) {
+    for (interaction, id) in delete_interaction_q.iter_mut() {
+        if *interaction == Interaction::Pressed {
+            actions.send(ModelTodoAction::Delete(*id));
+        }
+    }
+}
+
+/// Interaction -> Res<ClickTracker>
+fn track_clicks(
+    interaction_q: Query<(Entity, &Interaction), Changed<Interaction>>,
+    windows: Query<&Window, With<PrimaryWindow>>,
+    time: Res<Time>,
+    mut clicks: ResMut<ClickTracker>,
+) {
+    let now = time.elapsed();
+    clicks.forget_before(now);
+    let position = windows
+        .get_single()
+        .ok()
+        .and_then(|window| window.cursor_position());
+    for (entity, interaction) in interaction_q.iter() {
+        if *interaction == Interaction::Pressed {
+            clicks.press(entity, now, position);
+        }
+    }
+}
+
+/// Interaction + Res<ClickTracker> -> Event<ModelTodoAction> +  Event<ModelInputAction>
+#[allow(clippy::type_complexity)]
+fn handle_text_interaction(
+    mut check_interaction_q: Query<
+        (&Interaction, &TodoId, Entity),
+        (Changed<Interaction>, With<markers::TodoTextView>),
+    >,
+    todo_model: Query<(&ModelTodoEdit, &TodoId), ModelOnly>,
+    input_model: Query<(&ModelInputEdit, Entity), ModelOnly>,
+    clicks: Res<ClickTracker>,
+    mut todo_actions: EventWriter<ModelTodoAction>,
+    mut input_actions: EventWriter<ModelInputAction>,
+) {
+    for (interaction, view_id, view_entity) in check_interaction_q.iter_mut() {
+        if *interaction == Interaction::Pressed {
+            for (todo_edit, todo_id) in todo_model.iter() {
+                if todo_id == view_id {
+                    if !todo_edit.is_editing() && clicks.is_double_click(view_entity) {
+                        todo_actions.send(ModelTodoAction::Edit(*view_id, true));
+                    }
+                    continue;
+                }
+                if todo_edit.is_editing() {
+                    todo_actions.send(ModelTodoAction::Edit(*todo_id, false));
+                }
+            }
+            for (input_edit, todo_entity) in input_model.iter() {
+                if input_edit.0 {
+                    input_actions.send(ModelInputAction::Edit(todo_entity, false));
+                }
+            }
+        }
+    }
+}
+
+/// Interaction -> Event<ModelTodoAction> +  Event<ModelInputAction>
+#[allow(clippy::type_complexity)]
+fn handle_input_interaction(
+    mut check_interaction_q: Query<
+        (&Interaction, &View),
+        (Changed<Interaction>, With<markers::TodoInput>),
+    >,
+    todo_model: Query<(&ModelTodoEdit, &TodoId), ModelOnly>,
+    input_model: Query<(&ModelInputEdit, Entity), ModelOnly>,
+    mut todo_actions: EventWriter<ModelTodoAction>,
+    mut input_actions: EventWriter<ModelInputAction>,
+) {
+    for (interaction, view) in check_interaction_q.iter_mut() {
+        if *interaction == Interaction::Pressed {
+            for (todo_edit, todo_id) in todo_model.iter() {
+                if todo_edit.is_editing() {
+                    todo_actions.send(ModelTodoAction::Edit(*todo_id, false));
+                }
+            }
+            for (input_edit, input_entity) in input_model.iter() {
+                if input_entity == view.0 {
+                    if !input_edit.0 {
+                        input_actions.send(ModelInputAction::Edit(view.0, true));
+                    }
+                    continue;
+                }
+                if input_edit.0 {
+                    input_actions.send(ModelInputAction::Edit(input_entity, false));
+                }
+            }
+        }
+    }
+}
+
+/// Interaction -> Event<ModelTodoAction>
+#[allow(clippy::type_complexity)]
+fn handle_checkmark_interaction(
+    mut check_interaction_q: Query<
+        (&Interaction, &TodoId),
+        (Changed<Interaction>, With<markers::TodoCheckmarkView>),
+    >,
+    mut actions: EventWriter<ModelTodoAction>,
+) {
+    for (interaction, id) in check_interaction_q.iter_mut() {
+        if *interaction == Interaction::Pressed {
+            actions.send(ModelTodoAction::ToggleChecked(*id));
+        }
+    }
+}
+
+/// Interaction -> Event<ModelFilterAction>
+#[allow(clippy::type_complexity)]
+fn handle_filter_interaction(
+    filter_interaction_q: Query<
+        (
+            &Interaction,
+            &View,
+            Option<&markers::TodoFilterActive>,
+            Option<&markers::TodoFilterCompleted>,
+        ),
+        (
+            Changed<Interaction>,
+            Or<(
+                With<markers::TodoFilterAll>,
+                With<markers::TodoFilterActive>,
+                With<markers::TodoFilterCompleted>,
+            )>,
+        ),
+    >,
+    mut actions: EventWriter<ModelFilterAction>,
+) {
+    for (interaction, view, active, completed) in filter_interaction_q.iter() {
+        if *interaction == Interaction::Pressed {
+            actions.send(ModelFilterAction::Select(
+                view.0,
+                displayed_filter(active.is_some(), completed.is_some()),
+            ));
+        }
+    }
+}
+
+/// Interaction -> Event<ModelTodoAction>
+fn handle_clear_completed_interaction(
+    clear_interaction_q: Query<
+        &Interaction,
+        (Changed<Interaction>, With<markers::TodoClearCompleted>),
+    >,
+    mut actions: EventWriter<ModelTodoAction>,
+) {
+    for interaction in clear_interaction_q.iter() {
+        if *interaction == Interaction::Pressed {
+            actions.send(ModelTodoAction::ClearCompleted);
+        }
+    }
+}
+
+/// Interaction -> Event<ModelTodoAction>
+#[allow(clippy::type_complexity)]
+fn handle_toggle_all_interaction(
+    toggle_interaction_q: Query<
+        (&Interaction, &View),
+        (Changed<Interaction>, With<markers::TodoToggleAll>),
+    >,
+    model: Query<&DerivedModel<TodoAllChecked>, ModelOnly>,
+    mut actions: EventWriter<ModelTodoAction>,
+) {
+    for (interaction, view) in toggle_interaction_q.iter() {
+        if *interaction == Interaction::Pressed {
+            let Ok(all_checked) = model.get(view.0) else {
+                continue;
+            };
+            actions.send(ModelTodoAction::UpdateAllChecked(!all_checked.0 .0));
+        }
+    }
+}
+
+/// Input<KeyCode> + Res<Focus> -> Event<ModelTodoAction> + Event<ModelInputAction>
+///
+/// The editor's text is used when there is one,
+/// it is ahead of `ModelInputText` by up to a frame of `CosmicTextChanged`
+///
+/// But this system also directly updates the `Text` which it probably shouldn't (consider splitting)
+fn handle_enter(
+    keys: Res<Input<KeyCode>>,
+    focus: Res<Focus>,
+    mut todo_actions: EventWriter<ModelTodoAction>,
+    mut input_actions: EventWriter<ModelInputAction>,
+    mut todo_input_q: Query<(Option<&CosmicEditor>, &View), With<markers::TodoInput>>,
+    input_model: Query<&ModelInputText, ModelOnly>,
+    mut todo_q: Query<&TodoId, With<markers::TodoTextView>>,
+) {
+    let Some(focus) = **focus else {
+        return;
+    };
+    if keys.just_pressed(KeyCode::Return) {
+        if let Ok((editor, view)) = todo_input_q.get_mut(focus) {
+            let text = match editor {
+                Some(editor) => Some(editor.get_text()),
+                None => input_model.get(view.0).ok().map(|text| text.0.clone()),
+            };
+            if let Some(text) = text {
+                todo_actions.send(ModelTodoAction::Create(text));
+            }
+            input_actions.send(ModelInputAction::UpdateText(view.0, "".to_string()));
+        }
+        if let Ok(id) = todo_q.get_mut(focus) {
+            todo_actions.send(ModelTodoAction::Edit(*id, false));
+        }
+    }
+}
+
+/// Escape also takes the focus off the main input, so that Ctrl+Z undoes again
+///
+/// Input<KeyCode> + Res<Focus> -> Event<ModelTodoAction> + Event<SetFocus>
+fn handle_escape(
+    keys: Res<Input<KeyCode>>,
+    focus: Res<Focus>,
+    mut todo_actions: EventWriter<ModelTodoAction>,
+    mut set_focus: EventWriter<SetFocus>,
+    todo_q: Query<&TodoId, With<markers::TodoTextView>>,
+    todo_input_q: Query<(), With<markers::TodoInput>>,
+) {
+    let Some(focus) = **focus else {
+        return;
+    };
+    if keys.just_pressed(KeyCode::Escape) {
+        if let Ok(id) = todo_q.get(focus) {
+            todo_actions.send(ModelTodoAction::CancelEdit(*id));
+        }
+        if todo_input_q.contains(focus) {
+            set_focus.send(SetFocus(None));
+        }
+    }
+}
+
+/// Ctrl+Z in a focused editor belongs to its text, so undo only handles it without focus
+///
+/// Input<KeyCode> + Res<Focus> -> Event<HistoryAction>
+fn handle_undo(
+    keys: Res<Input<KeyCode>>,
+    focus: Res<Focus>,
+    mut history_actions: EventWriter<HistoryAction>,
+) {
+    if focus.is_some() {
+        return;
+    }
+    let ctrl = keys.any_pressed([
+        KeyCode::ControlLeft,
+        KeyCode::ControlRight,
+        KeyCode::SuperLeft,
+        KeyCode::SuperRight,
+    ]);
+    let shift = keys.any_pressed([KeyCode::ShiftLeft, KeyCode::ShiftRight]);
+    if ctrl && keys.just_pressed(KeyCode::Z) {
+        history_actions.send(if shift {
+            HistoryAction::Redo
+        } else {
+            HistoryAction::Undo
+        });
+    }
+}
+
+/// Event<SetFocus> -> Res<Focus>
+fn handle_focus(mut set_focus_events: EventReader<SetFocus>, mut focus: ResMut<Focus>) {
+    for ev in set_focus_events.iter() {
+        *focus = Focus(ev.0)
+    }
+}
+
+/// The question is how to express this at creation (setup_ui)
+///
+/// We want the equivalent of JS `input.addEventListener('oninput', (e) => { model.value = e.target.value })`
+///
+/// Event<CosmicTextChanged> -> Event<ModelInputAction> + Event<ModelTodoAction>
+fn handle_cosmic_change(
+    mut evr_cosmic: EventReader<CosmicTextChanged>,
+    todo_text_q: Query<&TodoId, With<markers::TodoTextView>>,
+    mut todo_input_q: Query<&View, (With<markers::TodoInput>, Without<markers::TodoTextView>)>,
+    mut todo_actions: EventWriter<ModelTodoAction>,
+    mut input_actions: EventWriter<ModelInputAction>,
+) {
+    for ev in evr_cosmic.iter() {
+        if let Ok(id) = todo_text_q.get(ev.0 .0) {
+            todo_actions.send(ModelTodoAction::UpdateText(*id, ev.0 .1.clone()));
+        }
+        if let Ok(view) = todo_input_q.get_mut(ev.0 .0) {
+            if ev.0 .1.clone().ends_with('\n') {
+                continue;
+            }
+            input_actions.send(ModelInputAction::UpdateText(view.0, ev.0 .1.clone()));
+        }
+    }
+}
+
+/// Flush after this
+///
+/// Event<ModelTodoAction> + Event<HistoryAction> -> Model + Res<History> + Event<ModelActionError>
+fn update_todo_model(
+    mut actions: EventReader<ModelTodoAction>,
+    mut history_actions: EventReader<HistoryAction>,
+    mut history: ResMut<History>,
+    mut model: TodoModel,
+    mut errors: EventWriter<ModelActionError>,
+) {
+    for action in actions.iter() {
+        match model.apply(action) {
+            Ok(inverse) => history.record(action, inverse),
+            Err(reason) => errors.send(ModelActionError::new(action.clone(), reason)),
+        }
+    }
+    for history_action in history_actions.iter() {
+        let entry = match history_action {
+            HistoryAction::Undo => history.undo.pop(),
+            HistoryAction::Redo => history.redo.pop(),
+        };
+        let Some(entry) = entry else {
+            continue;
+        };
+        let mut inverse = Vec::new();
+        for action in entry.iter() {
+            match model.apply(action) {
+                Ok(action_inverse) => inverse.extend(action_inverse),
+                Err(reason) => errors.send(ModelActionError::new(action.clone(), reason)),
+            }
+        }
+        // reverting has to happen in the reverse order of applying
+        inverse.reverse();
+        match history_action {
+            HistoryAction::Undo => history.redo.push(inverse),
+            HistoryAction::Redo => history.undo.push(inverse),
+        }
+        history.typing = None;
+    }
+}
+
+/// The todo model, as mutated by [`ModelTodoAction`]s
+#[derive(SystemParam)]
+struct TodoModel<'w, 's> {
+    commands: Commands<'w, 's>,
+    index: ResMut<'w, TodoIndex>,
+    text: Query<'w, 's, &'static mut ModelTodoText, ModelOnly>,
+    checked: Query<'w, 's, (&'static TodoId, &'static mut ModelTodoChecked), ModelOnly>,
+    edit: Query<'w, 's, &'static mut ModelTodoEdit, ModelOnly>,
+}
+
+impl TodoModel<'_, '_> {
+    /// Applies `action` to the model and returns the actions reverting it
+    fn apply(
+        &mut self,
+        action: &ModelTodoAction,
+    ) -> Result<Vec<ModelTodoAction>, ModelActionErrorReason> {
+        match action {
+            ModelTodoAction::Create(text) => {
+                let text = text.trim();
+                if text.is_empty() {
+                    return Ok(Vec::new());
+                }
+                let id = self.index.next_id();
+                spawn_todo(
+                    &mut self.commands,
+                    &mut self.index,
+                    id,
+                    text.to_string(),
+                    false,
+                );
+                Ok(vec![ModelTodoAction::Delete(id)])
+            }
+            ModelTodoAction::Restore(id, text, checked) => {
+                if self.index.get(*id).is_some() {
+                    return Err(ModelActionErrorReason::DuplicateTodo(*id));
+                }
+                spawn_todo(
+                    &mut self.commands,
+                    &mut self.index,
+                    *id,
+                    text.clone(),
+                    *checked,
+                );
+                Ok(vec![ModelTodoAction::Delete(*id)])
+            }
+            ModelTodoAction::Delete(id) => {
+                let e = self.entity(*id)?;
+                let inverse = self.restore_action(e)?;
+                self.despawn(*id, e);
+                Ok(vec![inverse])
+            }
+            ModelTodoAction::UpdateChecked(id, checked) => {
+                let e = self.entity(*id)?;
+                let (_, mut todo_checked) = self
+                    .checked
+                    .get_mut(e)
+                    .map_err(|_| ModelActionErrorReason::MissingModel(e))?;
+                let previous = std::mem::replace(&mut todo_checked.0, *checked);
+                if previous == *checked {
+                    return Ok(Vec::new());
+                }
+                Ok(vec![ModelTodoAction::UpdateChecked(*id, previous)])
+            }
+            ModelTodoAction::ToggleChecked(id) => {
+                let e = self.entity(*id)?;
+                let (_, mut todo_checked) = self
+                    .checked
+                    .get_mut(e)
+                    .map_err(|_| ModelActionErrorReason::MissingModel(e))?;
+                todo_checked.0 = !todo_checked.0;
+                Ok(vec![ModelTodoAction::UpdateChecked(*id, !todo_checked.0)])
+            }
+            ModelTodoAction::UpdateText(id, text) => {
+                let e = self.entity(*id)?;
+                let mut todo_text = self
+                    .text
+                    .get_mut(e)
+                    .map_err(|_| ModelActionErrorReason::MissingModel(e))?;
+                let previous = std::mem::replace(&mut todo_text.0, text.clone());
+                if previous == *text {
+                    return Ok(Vec::new());
+                }
+                Ok(vec![ModelTodoAction::UpdateText(*id, previous)])
+            }
+            ModelTodoAction::Edit(id, edit) => {
+                let e = self.entity(*id)?;
+                let text = self
+                    .text
+                    .get(e)
+                    .map_err(|_| ModelActionErrorReason::MissingModel(e))?;
+                let mut todo_edit = self
+                    .edit
+                    .get_mut(e)
+                    .map_err(|_| ModelActionErrorReason::MissingModel(e))?;
+                if *edit == todo_edit.is_editing() {
+                    return Ok(Vec::new());
+                }
+                // the text from before the edit, restored on cancel
+                let original = std::mem::replace(&mut todo_edit.0, edit.then(|| text.0.clone()));
+                if *edit {
+                    return Ok(Vec::new());
+                }
+
+                // committing the edit
+                let trimmed = text.0.trim().to_string();
+                if trimmed.is_empty() {
+                    // undoing brings the todo back as it was before the edit, not blank
+                    let (_, checked) = self
+                        .checked
+                        .get(e)
+                        .map_err(|_| ModelActionErrorReason::MissingModel(e))?;
+                    let restore = ModelTodoAction::Restore(
+                        *id,
+                        original.unwrap_or_else(|| text.0.clone()),
+                        checked.0,
+                    );
+                    self.despawn(*id, e);
+                    return Ok(vec![restore]);
+                }
+                if trimmed.len() == text.0.len() {
+                    return Ok(Vec::new());
+                }
+                self.apply(&ModelTodoAction::UpdateText(*id, trimmed))
+            }
+            ModelTodoAction::CancelEdit(id) => {
+                let e = self.entity(*id)?;
+                let mut todo_edit = self
+                    .edit
+                    .get_mut(e)
+                    .map_err(|_| ModelActionErrorReason::MissingModel(e))?;
+                let Some(original) = todo_edit.0.take() else {
+                    return Ok(Vec::new());
+                };
+                let mut todo_text = self
+                    .text
+                    .get_mut(e)
+                    .map_err(|_| ModelActionErrorReason::MissingModel(e))?;
+                if todo_text.0 == original {
+                    return Ok(Vec::new());
+                }
+                let previous = std::mem::replace(&mut todo_text.0, original);
+                Ok(vec![ModelTodoAction::UpdateText(*id, previous)])
+            }
+            ModelTodoAction::UpdateAllChecked(checked) => {
+                let mut inverse = Vec::new();
+                for (id, mut todo_checked) in self.checked.iter_mut() {
+                    if todo_checked.0 != *checked {
+                        todo_checked.0 = *checked;
+                        inverse.push(ModelTodoAction::UpdateChecked(*id, !*checked));
+                    }
+                }
+                Ok(inverse)
+            }
+            ModelTodoAction::ClearCompleted => {
+                let completed = self
+                    .checked
+                    .iter()
+                    .filter(|(_, checked)| checked.0)
+                    .map(|(id, _)| *id)
+                    .collect::<Vec<_>>();
+                // resolved up front, so an error despawns nothing instead of only some todos
+                let removed = completed
+                    .into_iter()
+                    .map(|id| -> Result<_, ModelActionErrorReason> {
+                        let e = self.entity(id)?;
+                        Ok((id, e, self.restore_action(e)?))
+                    })
+                    .collect::<Result<Vec<_>, _>>()?;
+                let mut inverse = Vec::new();
+                for (id, e, restore) in removed {
+                    inverse.push(restore);
+                    self.despawn(id, e);
+                }
+                Ok(inverse)
+            }
+        }
+    }
+
+    fn entity(&self, id: TodoId) -> Result<ModelTodoEntity, ModelActionErrorReason> {
+        self.index
+            .get(id)
+            .ok_or(ModelActionErrorReason::UnknownTodo(id))
+    }
+
+    fn despawn(&mut self, id: TodoId, e: ModelTodoEntity) {
+        self.index.remove(id);
+        if let Some(entity) = self.commands.get_entity(e) {
+            entity.despawn_recursive();
+        }
+    }
+
+    /// The action that brings back a todo after it is deleted
+    fn restore_action(
+        &self,
+        e: ModelTodoEntity,
+    ) -> Result<ModelTodoAction, ModelActionErrorReason> {
+        let missing = |_| ModelActionErrorReason::MissingModel(e);
+        let text = self.text.get(e).map_err(missing)?;
+        let (id, checked) = self.checked.get(e).map_err(missing)?;
+        Ok(ModelTodoAction::Restore(*id, text.0.clone(), checked.0))
+    }
+}
+
+/// Spawns a model (todo) and registers it in the [`TodoIndex`]
+fn spawn_todo(
+    commands: &mut Commands,
+    index: &mut TodoIndex,
+    id: TodoId,
+    text: String,
+    checked: bool,
+) -> ModelTodoEntity {
+    let e = commands
+        .spawn((
+            id,
+            ModelTodoText(text),
+            ModelTodoChecked(checked),
+            ModelTodoEdit(None),
+            Model,
+        ))
+        .id();
+    index.insert(id, e);
+    e
+}
+
+/// Flush after this
+///
+/// Event<ModelInputAction> -> Model + Event<ModelActionError>
+fn update_input_model(
+    mut commands: Commands,
+    mut actions: EventReader<ModelInputAction>,
+    mut input_text: Query<&mut ModelInputText, ModelOnly>,
+    mut input_edit: Query<&mut ModelInputEdit, ModelOnly>,
+    mut errors: EventWriter<ModelActionError>,
+) {
+    for action in actions.iter() {
+        match action {
+            ModelInputAction::Create(text) => {
+                commands.spawn((ModelInputText(text.clone()), ModelInputEdit(true), Model));
+            }
+            ModelInputAction::UpdateText(e, text) => match input_text.get_mut(*e) {
+                Ok(mut input_text) => input_text.0 = text.clone(),
+                Err(_) => errors.send(ModelActionError::new(
+                    action.clone(),
+                    ModelActionErrorReason::MissingModel(*e),
+                )),
+            },
+            ModelInputAction::Edit(e, edit) => match input_edit.get_mut(*e) {
+                Ok(mut input_edit) => input_edit.0 = *edit,
+                Err(_) => errors.send(ModelActionError::new(
+                    action.clone(),
+                    ModelActionErrorReason::MissingModel(*e),
+                )),
+            },
+        }
+    }
+}
+
+/// Flush after this
+///
+/// Event<ModelFilterAction> -> Model + Event<ModelActionError>
+fn update_filter_model(
+    mut actions: EventReader<ModelFilterAction>,
+    mut filter: Query<&mut ModelFilter, ModelOnly>,
+    mut errors: EventWriter<ModelActionError>,
+) {
+    for action in actions.iter() {
+        match action {
+            ModelFilterAction::Select(e, selected) => match filter.get_mut(*e) {
+                Ok(mut filter) => filter.0 = *selected,
+                Err(_) => errors.send(ModelActionError::new(
+                    action.clone(),
+                    ModelActionErrorReason::MissingModel(*e),
+                )),
+            },
+        }
+    }
+}
+
+/// Res<Route> -> Event<ModelFilterAction>
+fn update_filter_from_route(
+    route: Res<routing::Route>,
+    filter: Query<(Entity, &ModelFilter), ModelOnly>,
+    mut actions: EventWriter<ModelFilterAction>,
+) {
+    if !route.is_changed() {
+        return;
+    }
+    for (e, filter) in filter.iter() {
+        if filter.0 != route.0 {
+            actions.send(ModelFilterAction::Select(e, route.0));
+        }
+    }
+}
+
+// ModelFilter -> Res<Route>
+fn update_route_from_filter(
+    filter: Query<&ModelFilter, (Changed<ModelFilter>, ModelOnly)>,
+    mut route: ResMut<routing::Route>,
+) {
+    for filter in filter.iter() {
+        route.set_if_neq(routing::Route(filter.0));
+    }
+}
+
+/// Event<ModelActionError> -> Log
+fn log_model_action_errors(mut errors: EventReader<ModelActionError>) {
+    for error in errors.iter() {
+        warn!("rejected {:?}: {}", error.action, error.reason);
+    }
+}
+
+/// Whenever a model (input) is created
+/// display it by creating a view and appending it to the target parent view
+///
+/// ModelInputText -> View + Event<SetFocus>
+fn display_text_input(
+    inputs: Query<(ModelInputEntity, &ModelInputText), (Added<ModelInputText>, ModelOnly)>,
+    todo_input_container: Query<Entity, With<markers::TodoInputContainer>>,
+    mut commands: Commands,
+    mut set_focus: EventWriter<SetFocus>,
+    windows: Query<&Window, With<PrimaryWindow>>,
+    theme: Res<Theme>,
+) {
+    let todo_input_container = todo_input_container.single();
+    for (model_entity, input) in inputs.iter() {
+        let text_color = theme.text.todo.color;
+        let attrs = AttrsOwned::new(Attrs::new().color(bevy_color_to_cosmic(text_color)));
+        let placeholder_attrs =
+            AttrsOwned::new(Attrs::new().color(bevy_color_to_cosmic(theme.text.placeholder.color)));
+        let todo_input_btn = commands
+            .spawn((
+                CosmicEditUiBundle {
+                    fill_color: FillColor(theme.palette.todoapp_background),
+                    #[cfg(feature = "debug")]
+                    border_color: Color::GREEN.into(),
+                    style: Style {
+                        height: Val::Px(40.),
+                        padding: UiRect::all(Val::Px(10.)),
+                        width: Val::Percent(100.),
+                        border: UiRect::all(Val::Px(4.)),
+                        ..default()
+                    },
+                    cosmic_attrs: CosmicAttrs(attrs.clone()),
+                    cosmic_metrics: CosmicMetrics {
+                        font_size: theme.text.todo.font_size,
+                        line_height: theme.text.todo.font_size * 1.2,
+                        scale_factor: scale_factor(&windows),
+                    },
+                    text_setter: CosmicText::OneStyle(input.0.clone()),
+                    text_position: text_position(&theme),
+                    placeholder_attrs: PlaceholderAttrs(placeholder_attrs.clone()),
+                    placeholder_setter: PlaceholderText(CosmicText::OneStyle(String::new())),
+                    mode: CosmicMode::InfiniteLine,
+                    ..default()
+                },
+                View(model_entity),
+                markers::TodoInput,
+                l10n::Localized::new("new-todo-placeholder"),
+                Styled::Input,
+            ))
+            .id();
+
+        todo_input_container
+            .tree(todo_input_btn)
+            .build(&mut commands);
+        set_focus.send(SetFocus(Some(todo_input_btn)));
+    }
+}
+
+/// Helper function
+fn display_checked_text(checked: &ModelTodoChecked) -> String {
+    if checked.0 {
+        "\u{e92d}".to_string()
+    } else {
+        "\u{e836}".to_string()
+    }
+}
+
+/// Helper function
+fn display_checked_style(
+    theme: &Theme,
+    checked: &ModelTodoChecked,
+    font: Handle<Font>,
+) -> TextStyle {
+    if checked.0 {
+        theme.text.checkmark_completed.with_font(font)
+    } else {
+        theme.text.checkmark.with_font(font)
+    }
+}
+
+/// Helper function
+fn display_todo_color(theme: &Theme, checked: &ModelTodoChecked) -> Color {
+    if checked.0 {
+        theme.palette.todo_list_item_completed_color
+    } else {
+        theme.text.todo.color
+    }
+}
+
+/// Like a themed binding, except that the text being edited keeps the color it gets from `todo_row`
+///
+/// ModelTodoChecked + ModelTodoEdit + Res<Theme> -> View
+fn update_todo_text_color(
+    models: Query<(Ref<ModelTodoChecked>, &ModelTodoEdit), ModelOnly>,
+    mut views: Query<(&mut CosmicAttrs, &View), (With<markers::TodoTextView>, ViewOnly)>,
+    theme: Res<Theme>,
+) {
+    for (mut attrs, view) in views.iter_mut() {
+        if let Ok((checked, edit)) = models.get(view.0) {
+            if !checked.is_changed() && !theme.is_changed() {
+                continue;
+            }
+            let color = if edit.is_editing() {
+                theme.text.todo.color
+            } else {
+                display_todo_color(&theme, &checked)
+            };
+            attrs.0.color_opt = Some(bevy_color_to_cosmic(color));
+        }
+    }
+}
+
+/// Whenever a model (todo) is created,
+/// display it by creating a view and appending it to the target parent view
+///
+/// ModelTodo{Text,Checked} -> View
+#[allow(clippy::type_complexity)]
+fn display_todos(
+    todos: Query<
+        (
+            ModelTodoEntity,
+            &TodoId,
+            &ModelTodoText,
+            &ModelTodoChecked,
+            &ModelTodoEdit,
+        ),
+        (Added<ModelTodoText>, Added<ModelTodoChecked>, ModelOnly),
+    >,
+    todo_list_q: Query<Entity, With<markers::TodoList>>,
+    todo_rows: Query<&TodoId, (With<markers::TodoRootView>, ViewOnly)>,
+    mut commands: Commands,
+    windows: Query<&Window, With<PrimaryWindow>>,
+    fonts: Res<Fonts>,
+    theme: Res<Theme>,
+) {
+    // an outer reference
+    let todo_list = todo_list_q.single();
+    // rows are kept sorted by id, so that e.g. a restored todo lands back in its spot
+    let mut displayed = todo_rows.iter().copied().collect::<Vec<_>>();
+    displayed.sort();
+    let mut todos = todos.iter().collect::<Vec<_>>();
+    todos.sort_by_key(|(_, id, ..)| **id);
+    // some loop
+    for (model_entity, id, text, checked, edit) in todos {
+        let index = displayed.partition_point(|displayed| displayed < id);
+        displayed.insert(index, *id);
+        // constructing a view
+        node((
+            NodeBundle {
+                #[cfg(feature = "debug")]
+                background_color: Color::RED.into(),
+                style: Style {
+                    border: UiRect::all(Val::Px(2.0)),
+                    justify_content: JustifyContent::SpaceBetween,
+                    align_items: AlignItems::Center,
+                    width: Val::Percent(100.),
+                    ..default()
+                },
+                border_color: theme.palette.todo_list_item_border_bottom.into(),
+                ..default()
+            },
+            View(model_entity),
+            *id,
+            markers::TodoRootView,
+            // only to find out when the row is hovered, see `update_show_on_hover`
+            Interaction::default(),
+            Styled::Row,
+        ))
+        .tree(todo_row(
+            model_entity,
+            *id,
+            text,
+            checked,
+            edit.is_editing(),
+            scale_factor(&windows),
+            &fonts,
+            &theme,
+        ))
+        .insert(&mut commands, todo_list, index);
+    }
+}
+
+/// The children of a todo's root view
+///
+/// Only the text view is shown while editing,
+/// it is the same entity in both modes so that the editor keeps its state.
+#[allow(clippy::too_many_arguments)]
+fn todo_row(
+    model_entity: Entity,
+    id: TodoId,
+    text: &ModelTodoText,
+    checked: &ModelTodoChecked,
+    is_editing: bool,
+    scale_factor: f32,
+    fonts: &Fonts,
+    theme: &Theme,
+) -> Vec<tree_builder::Tree> {
+    let todo_text_btn = (
+        CosmicEditUiBundle {
+            fill_color: FillColor(theme.palette.todoapp_background),
+            cosmic_metrics: CosmicMetrics {
+                font_size: theme.text.todo.font_size,
+                line_height: theme.text.todo.font_size * 1.2,
+                scale_factor,
+            },
+            text_setter: CosmicText::OneStyle(text.0.clone()),
+            text_position: text_position(theme),
+            ..default()
+        },
+        View(model_entity),
+        id,
+        markers::TodoTextView,
+        Styled::TodoText,
+    );
+
+    if is_editing {
+        let text_color = theme.text.todo.color;
+        let attrs = AttrsOwned::new(Attrs::new().color(bevy_color_to_cosmic(text_color)));
+        return ui![
+            todo_text_btn: todo_text_btn;
+                insert((
+                    Style {
+                        border: UiRect::all(Val::Px(2.0)),
+                        margin: UiRect {
+                            left: Val::Px(40.),
+                            ..default()
+                        },
+                        width: Val::Percent(100.),
+                        height: Val::Px(55.),
+                        ..default()
+                    },
+                    BorderColor(theme.palette.edit_border),
+                    CosmicAttrs(attrs),
+                    CosmicMode::InfiniteLine,
+                ));
+                remove::<ReadOnly>(),
+        ];
+    }
+
+    let attrs = AttrsOwned::new(
+        Attrs::new().color(bevy_color_to_cosmic(display_todo_color(theme, checked))),
+    );
+    ui![
+        todo_check_btn: (
+            ButtonBundle {
+                #[cfg(feature = "debug")]
+                background_color: Color::BLUE.into(),
+                #[cfg(not(feature = "debug"))]
+                background_color: theme.palette.todoapp_background.into(),
+                style: Style {
+                    width: Val::Px(40.),
+                    height: Val::Px(40.),
+                    justify_content: JustifyContent::Center,
+                    align_items: AlignItems::Center,
+                    overflow: Overflow::clip(),
+                    ..default()
+                },
+                ..default()
+            },
+            View(model_entity),
+            id,
+            markers::TodoCheckmarkView,
+            Styled::Button,
+        ) => [
+            todo_check_txt: (
+                TextBundle {
+                    #[cfg(feature = "debug")]
+                    background_color: Color::FUCHSIA.into(),
+                    text: Text::from_sections([TextSection::new(
+                        display_checked_text(checked),
+                        display_checked_style(theme, checked, fonts.icons.clone()),
+                    )]),
+                    ..default()
+                },
+                View(model_entity),
+                id,
+                markers::TodoCheckmarkView,
+            ),
+        ],
+        todo_text_btn: todo_text_btn;
+            insert((
+                Style {
+                    width: Val::Percent(100.),
+                    height: Val::Px(40.),
+                    ..default()
+                },
+                CosmicAttrs(attrs),
+                CosmicMode::AutoHeight,
+                ReadOnly,
+            )),
+        todo_delete_btn: (
+            ButtonBundle {
+                #[cfg(feature = "debug")]
+                background_color: Color::YELLOW.into(),
+                #[cfg(not(feature = "debug"))]
+                background_color: theme.palette.todoapp_background.into(),
+                style: Style {
+                    justify_content: JustifyContent::Center,
+                    align_items: AlignItems::Center,
+                    width: Val::Px(40.),
+                    height: Val::Px(40.),
+                    overflow: Overflow::clip(),
+                    ..default()
+                },
+                visibility: Visibility::Hidden,
+                ..default()
+            },
+            ReadOnly,
+            View(model_entity),
+            id,
+            markers::TodoDeleterView,
+            ShowOnHover,
+            HoverTextColor {
+                normal: theme.palette.todo_list_item_destroy_color,
+                hovered: theme.palette.todo_list_item_destroy_hover_color,
+            },
+            Styled::Deleter,
+        ) => [
+            todo_delete_txt: (
+                TextBundle {
+                    #[cfg(feature = "debug")]
+                    background_color: Color::TURQUOISE.into(),
+                    text: Text::from_section("x", theme.text.destroy.with_font(fonts.text.clone())),
+                    ..default()
+                },
+                View(model_entity),
+                id,
+                Styled::DeleterText,
+            ),
+        ],
+    ]
+}
+
+// Res<Locale> + Localized -> View
+fn update_localized_text(
+    mut localizer: l10n::Localizer,
+    mut views: Query<(Ref<l10n::Localized>, &mut Text)>,
+) {
+    let all = localizer.is_changed();
+    for (localized, mut text) in views.iter_mut() {
+        if !all && !localized.is_changed() {
+            continue;
+        }
+        if let Some(message) = localizer.localize(&localized) {
+            text.sections[0].value = message;
+        }
+    }
+}
+
+// Res<Locale> + Localized -> View
+fn update_localized_placeholder(
+    mut localizer: l10n::Localizer,
+    mut views: Query<(Ref<l10n::Localized>, &mut PlaceholderText)>,
+) {
+    let all = localizer.is_changed();
+    for (localized, mut placeholder) in views.iter_mut() {
+        if !all && !localized.is_changed() {
+            continue;
+        }
+        if let Some(message) = localizer.localize(&localized) {
+            placeholder.0 = CosmicText::OneStyle(message);
+        }
+    }
+}
+
+// Res<Locale> -> Window
+fn update_localized_window_title(
+    mut localizer: l10n::Localizer,
+    mut windows: Query<&mut Window, With<PrimaryWindow>>,
+) {
+    if !localizer.is_changed() {
+        return;
+    }
+    let Some(title) = localizer.localize(&l10n::Localized::new("window-title")) else {
+        return;
+    };
+    for mut window in windows.iter_mut() {
+        window.title = title.clone();
+    }
+}
+
+// DerivedModel<TodoCompletedCount> -> View
+fn update_displayed_clear_completed(
+    completed_count: Query<
+        &DerivedModel<TodoCompletedCount>,
+        (Changed<DerivedModel<TodoCompletedCount>>, ModelOnly),
+    >,
+    mut views: Query<(&mut Style, &View), (With<markers::TodoClearCompleted>, ViewOnly)>,
+) {
+    for (mut style, view) in views.iter_mut() {
+        if let Ok(count) = completed_count.get(view.0) {
+            style.display = if count.0 .0 > 0 {
+                Display::Flex
+            } else {
+                Display::None
+            };
+        }
+    }
+}
+
+// DerivedModel<TodoAllChecked> + Res<Theme> -> View
+fn update_displayed_toggle_all(
+    all_checked: Query<Ref<DerivedModel<TodoAllChecked>>, ModelOnly>,
+    mut views: Query<(&mut Text, &View), (With<markers::TodoToggleAll>, ViewOnly)>,
+    fonts: Res<Fonts>,
+    theme: Res<Theme>,
+) {
+    for (mut text, view) in views.iter_mut() {
+        let Ok(all_checked) = all_checked.get(view.0) else {
+            continue;
+        };
+        if !all_checked.is_changed() && !theme.is_changed() {
+            continue;
+        }
+        text.sections[0].style = if all_checked.0 .0 {
+            theme.text.toggle_all_checked.with_font(fonts.text.clone())
+        } else {
+            theme.text.toggle_all.with_font(fonts.text.clone())
+        };
+    }
+}
+
+/// Whenever a view is hovered or left, the views of the same model that are only shown on hover are updated
+///
+/// Interaction -> View
+fn update_show_on_hover(
+    changed: Query<(), (Changed<Interaction>, ViewOnly)>,
+    interactions: Query<(&Interaction, &View), ViewOnly>,
+    mut views: Query<(&mut Visibility, &View), (With<ShowOnHover>, ViewOnly)>,
+) {
+    if changed.is_empty() {
+        return;
+    }
+    let hovered_models = interactions
+        .iter()
+        .filter(|(interaction, _)| **interaction != Interaction::None)
+        .map(|(_, view)| view.0)
+        .collect::<std::collections::HashSet<_>>();
+    for (mut visibility, view) in views.iter_mut() {
+        let target = if hovered_models.contains(&view.0) {
+            Visibility::Inherited
+        } else {
+            Visibility::Hidden
+        };
+        if *visibility != target {
+            *visibility = target;
+        }
+    }
+}
+
+/// Interaction -> View
+fn update_hover_text_color(
+    buttons: Query<(&Interaction, &Children, &HoverTextColor), Changed<Interaction>>,
+    mut texts: Query<&mut Text>,
+) {
+    for (interaction, children, hover_color) in buttons.iter() {
+        let color = if *interaction == Interaction::None {
+            hover_color.normal
+        } else {
+            hover_color.hovered
+        };
+        let mut iter = texts.iter_many_mut(children);
+        while let Some(mut text) = iter.fetch_next() {
+            for section in text.sections.iter_mut() {
+                section.style.color = color;
+            }
+        }
+    }
+}
+
+/// Colours, sizes and padding of views that only depend on the theme and not on a model,
+/// model-dependent colours follow the theme in their own binds
+///
+/// Res<Theme> -> View
+#[allow(clippy::type_complexity)]
+fn restyle(
+    theme: Res<Theme>,
+    mut views: Query<(
+        &Styled,
+        Option<&mut BackgroundColor>,
+        Option<&mut BorderColor>,
+        Option<&mut Text>,
+        Option<&mut HoverTextColor>,
+        Option<&mut FillColor>,
+        Option<&mut CosmicAttrs>,
+        Option<&mut PlaceholderAttrs>,
+        Option<&mut CosmicTextPosition>,
+        Option<&mut CosmicMetrics>,
+    )>,
+) {
+    if !theme.is_changed() {
+        return;
+    }
+    let (palette, text_styles) = (&theme.palette, &theme.text);
+    // outer loop, library-provided
+    for (styled, background, border, text, hover, fill, attrs, placeholder, position, metrics) in
+        views.iter_mut()
+    {
+        // inner logic, user-provided
+        match styled {
+            Styled::Page => update_if_some(background, |c| c.0 = palette.body_background),
+            Styled::App | Styled::Button => {
+                update_if_some(background, |c| c.0 = palette.todoapp_background)
+            }
+            Styled::Title => update_if_some(text, |t| restyle_text(t, text_styles.title)),
+            Styled::Footer => update_if_some(text, |t| restyle_text(t, text_styles.footer)),
+            Styled::List => update_if_some(border, |c| c.0 = palette.main_border_top),
+            Styled::Row => update_if_some(border, |c| c.0 = palette.todo_list_item_border_bottom),
+            Styled::Deleter => {
+                update_if_some(background, |c| c.0 = palette.todoapp_background);
+                update_if_some(hover, |h| {
+                    h.normal = palette.todo_list_item_destroy_color;
+                    h.hovered = palette.todo_list_item_destroy_hover_color;
+                });
+            }
+            Styled::DeleterText => update_if_some(text, |t| restyle_text(t, text_styles.destroy)),
+            Styled::Input => {
+                update_if_some(fill, |c| c.0 = palette.todoapp_background);
+                update_if_some(position, |p| *p = text_position(&theme));
+                update_if_some(metrics, |m| restyle_metrics(m, text_styles.todo));
+                update_if_some(attrs, |a| {
+                    a.0.color_opt = Some(bevy_color_to_cosmic(text_styles.todo.color));
+                });
+                update_if_some(placeholder, |a| {
+                    a.0.color_opt = Some(bevy_color_to_cosmic(text_styles.placeholder.color));
+                });
+            }
+            Styled::TodoText => {
+                update_if_some(fill, |c| c.0 = palette.todoapp_background);
+                update_if_some(position, |p| *p = text_position(&theme));
+                update_if_some(metrics, |m| restyle_metrics(m, text_styles.todo));
+                update_if_some(border, |c| c.0 = palette.edit_border);
+            }
+        }
+    }
+}
+
+/// Helper function
+fn update_if_some<T: Component>(component: Option<Mut<T>>, update: impl FnOnce(&mut T)) {
+    if let Some(mut component) = component {
+        update(&mut component);
+    }
+}
+
+/// Helper function
+fn text_position(theme: &Theme) -> CosmicTextPosition {
+    CosmicTextPosition::Left {
+        padding: theme.spacing.text_padding,
+    }
+}
+
+/// Helper function
+fn restyle_metrics(metrics: &mut CosmicMetrics, style: ThemeText) {
+    metrics.font_size = style.font_size;
+    metrics.line_height = style.font_size * 1.2;
+}
+
+/// Helper function
+fn restyle_text(text: &mut Text, style: ThemeText) {
+    for section in text.sections.iter_mut() {
+        section.style.font_size = style.font_size;
+        section.style.color = style.color;
+    }
+}
+
+/// Like TodoMVC's `.main`, the toggle-all chevron is hidden together with the list,
+/// it keeps its place so the input doesn't move
+///
+/// DerivedModel<TodoTotalCount> -> View
+#[allow(clippy::type_complexity)]
+fn update_displayed_list_and_footer(
+    total_count: Query<
+        &DerivedModel<TodoTotalCount>,
+        (Changed<DerivedModel<TodoTotalCount>>, ModelOnly),
+    >,
+    mut views: Query<
+        (&mut Style, &View),
+        (
+            Or<(With<markers::TodoList>, With<markers::TodoFooter>)>,
+            ViewOnly,
+        ),
+    >,
+    mut toggle_all: Query<&mut Visibility, (With<markers::TodoToggleAll>, With<Button>, ViewOnly)>,
+) {
+    for (mut style, view) in views.iter_mut() {
+        if let Ok(count) = total_count.get(view.0) {
+            style.display = if count.0 .0 > 0 {
+                Display::Flex
+            } else {
+                Display::None
+            };
+        }
+    }
+    for count in total_count.iter() {
+        let visibility = if count.0 .0 > 0 {
+            Visibility::Inherited
+        } else {
+            Visibility::Hidden
+        };
+        for mut toggle_all in toggle_all.iter_mut() {
+            toggle_all.set_if_neq(visibility);
+        }
+    }
+}
+
+pub fn bevy_color_to_cosmic(color: bevy::prelude::Color) -> CosmicColor {
+    CosmicColor::rgba(
+        (color.r() * 255.) as u8,
+        (color.g() * 255.) as u8,
+        (color.b() * 255.) as u8,
+        (color.a() * 255.) as u8,
+    )
+}
+
+/// cosmic-edit only applies `CosmicAttrs` when the text is set, so it is set again
+///
+/// TODO: Remove this hack. Figure out why set_redraw to true doesn't work in this case.
+///
+/// View -> View
+fn redraw_changed_attrs(mut views: Query<&mut CosmicText, (Changed<CosmicAttrs>, ViewOnly)>) {
+    for mut text in views.iter_mut() {
+        text.set_changed();
+    }
+}
+
+/// Helper function
+fn displayed_filter(active: bool, completed: bool) -> TodoFilter {
+    if active {
+        TodoFilter::Active
+    } else if completed {
+        TodoFilter::Completed
+    } else {
+        TodoFilter::All
+    }
+}
+
+// ModelFilter + Res<Theme> -> View
+#[allow(clippy::type_complexity)]
+fn update_displayed_filter_buttons(
+    model_filter: Query<Ref<ModelFilter>, ModelOnly>,
+    mut views: Query<
+        (
+            &mut BorderColor,
+            &View,
+            Option<&markers::TodoFilterActive>,
+            Option<&markers::TodoFilterCompleted>,
+        ),
+        (
+            ViewOnly,
+            Or<(
+                With<markers::TodoFilterAll>,
+                With<markers::TodoFilterActive>,
+                With<markers::TodoFilterCompleted>,
+            )>,
+        ),
+    >,
+    theme: Res<Theme>,
+) {
+    // outer loop, library-provided
+    for (mut border_color, view, active, completed) in views.iter_mut() {
+        if let Ok(filter) = model_filter.get(view.0) {
+            if !filter.is_changed() && !theme.is_changed() {
+                continue;
+            }
+            // inner logic, user-provided
+            border_color.0 = if filter.0 == displayed_filter(active.is_some(), completed.is_some())
+            {
+                theme.palette.filters_li_a_selected
+            } else {
+                Color::NONE
+            };
+        }
+    }
+}
+
+/// Whenever the filter or a model (todo.checked) is updated, or a todo is displayed,
+/// the todos that don't pass the filter are hidden
+///
+/// ModelFilter + ModelTodoChecked -> View
+#[allow(clippy::type_complexity)]
+fn update_displayed_todos_visibility(
+    model_filter: Query<Ref<ModelFilter>, ModelOnly>,
+    model_todo_checked: Query<Ref<ModelTodoChecked>, ModelOnly>,
+    mut views: Query<(Ref<View>, &mut Style), (ViewOnly, With<markers::TodoRootView>)>,
+) {
+    let Ok(filter) = model_filter.get_single() else {
+        return;
+    };
+    for (view, mut style) in views.iter_mut() {
+        let Ok(checked) = model_todo_checked.get(view.0) else {
+            continue;
+        };
+        if !filter.is_changed() && !checked.is_changed() && !view.is_added() {
+            continue;
+        }
+        let visible = match filter.0 {
+            TodoFilter::All => true,
+            TodoFilter::Active => !checked.0,
+            TodoFilter::Completed => checked.0,
+        };
+        style.display = if visible {
+            Display::Flex
+        } else {
+            Display::None
+        };
+    }
+}
+
+// ModelInputEdit -> View + Event<SetFocus>
+fn update_focus_main_input(
+    model_input_edit: Query<(&ModelInputEdit, Entity), (Changed<ModelInputEdit>, ModelOnly)>,
+    views: Query<(Entity, &View), (ViewOnly, With<markers::TodoInput>)>,
+    mut set_focus: EventWriter<SetFocus>,
+    mut commands: Commands,
+) {
+    let models_to_views = views
+        .iter()
+        .map(|(entity, view)| (view.0, entity))
+        .collect::<std::collections::HashMap<_, _>>();
+    for (edit, model_entity) in model_input_edit.iter() {
+        if let Some(view_entity) = models_to_views.get(&model_entity) {
+            if edit.0 {
+                set_focus.send(SetFocus(Some(*view_entity)));
+                commands.entity(*view_entity).remove::<ReadOnly>();
+            } else {
+                commands.entity(*view_entity).insert(ReadOnly);
+            }
+        }
+    }
+}
+
+/// Whenever a todo enters or leaves edit mode, its row is reconciled with the other mode,
+/// reusing the text view
+///
+/// ModelTodoEdit + Res<Focus> -> View + Event<SetFocus>
+#[allow(clippy::type_complexity, clippy::too_many_arguments)]
+fn update_focus_todo(
+    model_todo_edit: Query<
+        (
+            &ModelTodoEdit,
+            &ModelTodoChecked,
+            &ModelTodoText,
+            &TodoId,
+            Entity,
+        ),
+        (Changed<ModelTodoEdit>, ModelOnly),
+    >,
+    root_views: Query<(Entity, &View), (ViewOnly, With<markers::TodoRootView>)>,
+    keyed: tree_builder::Keyed,
+    focus: Res<Focus>,
+    mut set_focus: EventWriter<SetFocus>,
+    windows: Query<&Window, With<PrimaryWindow>>,
+    mut commands: Commands,
+    fonts: Res<Fonts>,
+    theme: Res<Theme>,
+) {
+    let models_to_views = root_views
+        .iter()
+        .map(|(entity, view)| (view.0, entity))
+        .collect::<std::collections::HashMap<_, _>>();
+    for (edit, checked, text, id, model_entity) in model_todo_edit.iter() {
+        if let Some(root_view_entity) = models_to_views.get(&model_entity) {
+            let row = todo_row(
+                model_entity,
+                *id,
+                text,
+                checked,
+                edit.is_editing(),
+                scale_factor(&windows),
+                &fonts,
+                &theme,
+            );
+            let children = tree_builder::reconcile(&mut commands, &keyed, *root_view_entity, row);
+            let text_view = children.get("todo_text_btn").copied();
+            if edit.is_editing() {
+                set_focus.send(SetFocus(text_view));
+            } else if focus.is_some() && **focus == text_view {
+                // the text is read-only again, and Ctrl+Z goes back to undoing
+                set_focus.send(SetFocus(None));
+            }
+        }
+    }
+}
+
+/// `--name <value>` or `--name=<value>` from the command line, used for `--route` and by the launcher
+#[doc(hidden)]
+pub fn command_line_arg(name: &str) -> Option<String> {
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        if arg == name {
+            return args.next();
+        }
+        if let Some(value) = arg.strip_prefix(name).and_then(|v| v.strip_prefix('=')) {
+            return Some(value.to_string());
+        }
+    }
+    None
+}
+
+/// Input handling is skipped while a recording is replayed
+fn live_input(replay: Option<Res<recording::Replay>>) -> bool {
+    replay.map_or(true, |replay| replay.is_finished())
+}
+
+/// Model -> Recording
+fn write_recording_header(
+    mut recorder: ResMut<recording::Recorder>,
+    todos: Query<(&TodoId, &ModelTodoText, &ModelTodoChecked), ModelOnly>,
+) {
+    let mut todos = todos
+        .iter()
+        .map(|(id, text, checked)| persistence::PersistedTodo {
+            id: *id,
+            text: text.0.clone(),
+            checked: checked.0,
+        })
+        .collect::<Vec<_>>();
+    todos.sort_by_key(|todo| todo.id);
+    recorder.write_header(&recording::Header { todos });
+}
+
+/// Events sent outside of input handling, e.g. actions sent by views,
+/// follow from the recorded ones and are not recorded themselves
+fn skip_unrecorded_events(
+    mut recorder: ResMut<recording::Recorder>,
+    todo_actions: Res<Events<ModelTodoAction>>,
+    input_actions: Res<Events<ModelInputAction>>,
+    filter_actions: Res<Events<ModelFilterAction>>,
+    history_actions: Res<Events<HistoryAction>>,
+) {
+    recorder.todo.clear(&todo_actions);
+    recorder.input.clear(&input_actions);
+    recorder.filter.clear(&filter_actions);
+    recorder.history.clear(&history_actions);
+}
+
+/// Input + Event<ModelTodoAction> + Event<ModelInputAction> + Event<ModelFilterAction>
+/// + Event<HistoryAction> -> Recording
+#[allow(clippy::too_many_arguments)]
+fn record_frame(
+    mut recorder: ResMut<recording::Recorder>,
+    todo_actions: Res<Events<ModelTodoAction>>,
+    input_actions: Res<Events<ModelInputAction>>,
+    filter_actions: Res<Events<ModelFilterAction>>,
+    history_actions: Res<Events<HistoryAction>>,
+    keyboard: Option<Res<Events<KeyboardInput>>>,
+    mouse_buttons: Option<Res<Events<MouseButtonInput>>>,
+    cursor: Option<Res<Events<CursorMoved>>>,
+) {
+    let recorder = &mut *recorder;
+    let mut frame = recording::Frame::default();
+    if let Some(keyboard) = keyboard {
+        frame.raw.extend(
+            recorder
+                .keyboard
+                .iter(&keyboard)
+                .map(|ev| recording::RawInput::Key {
+                    scan_code: ev.scan_code,
+                    key_code: ev.key_code,
+                    pressed: ev.state == ButtonState::Pressed,
+                }),
+        );
+    }
+    if let Some(mouse_buttons) = mouse_buttons {
+        frame
+            .raw
+            .extend(recorder.mouse_button.iter(&mouse_buttons).map(|ev| {
+                recording::RawInput::MouseButton {
+                    button: ev.button,
+                    pressed: ev.state == ButtonState::Pressed,
+                }
+            }));
+    }
+    if let Some(cursor) = cursor {
+        frame.raw.extend(
+            recorder
+                .cursor
+                .iter(&cursor)
+                .map(|ev| recording::RawInput::Cursor(ev.position)),
+        );
+    }
+    frame.todo = recorder.todo.iter(&todo_actions).cloned().collect();
+    frame.input = recorder
+        .input
+        .iter(&input_actions)
+        .map(Into::into)
+        .collect();
+    frame.filter = recorder
+        .filter
+        .iter(&filter_actions)
+        .map(|ModelFilterAction::Select(_, filter)| *filter)
+        .collect();
+    frame.history = recorder.history.iter(&history_actions).cloned().collect();
+    recorder.record(frame);
+}
+
+/// Recording -> Model
+fn load_replayed_todos(
+    mut commands: Commands,
+    mut index: ResMut<TodoIndex>,
+    replay: Res<recording::Replay>,
+) {
+    for todo in replay.todos() {
+        index.reserve(todo.id);
+        spawn_todo(
+            &mut commands,
+            &mut index,
+            todo.id,
+            todo.text.clone(),
+            todo.checked,
+        );
+    }
+}
+
+/// Recording -> Event<ModelTodoAction> + Event<ModelInputAction> + Event<ModelFilterAction>
+/// + Event<HistoryAction>
+fn replay_frame(
+    mut replay: ResMut<recording::Replay>,
+    mut todo_actions: EventWriter<ModelTodoAction>,
+    mut input_actions: EventWriter<ModelInputAction>,
+    mut filter_actions: EventWriter<ModelFilterAction>,
+    mut history_actions: EventWriter<HistoryAction>,
+    input_model: Query<Entity, (With<ModelInputText>, ModelOnly)>,
+    filter_model: Query<Entity, (With<ModelFilter>, ModelOnly)>,
+) {
+    if let Some(frame) = replay.current() {
+        todo_actions.send_batch(frame.todo.iter().cloned());
+        if let Ok(e) = input_model.get_single() {
+            input_actions.send_batch(frame.input.iter().map(|action| action.with_model(e)));
+        }
+        if let Ok(e) = filter_model.get_single() {
+            filter_actions.send_batch(
+                frame
+                    .filter
+                    .iter()
+                    .map(|filter| ModelFilterAction::Select(e, *filter)),
+            );
+        }
+        history_actions.send_batch(frame.history.iter().cloned());
+    }
+    replay.advance();
+}
+
+/// Todos stored without an id get fresh ones, after the stored ids
+///
+/// Storage -> Model
+fn load_todos(mut commands: Commands, mut index: ResMut<TodoIndex>) {
+    let todos = persistence::load();
+    for id in todos.iter().filter_map(|todo| todo.id) {
+        index.reserve(id);
+    }
+    for todo in todos {
+        let id = todo.id.unwrap_or_else(|| index.next_id());
+        spawn_todo(&mut commands, &mut index, id, todo.text, todo.checked);
+    }
+}
+
+/// Whenever a model (todo) is created, updated or removed, all todos are written to storage
+///
+/// Model -> Storage
+fn save_todos(
+    text_changed: Query<(), (Changed<ModelTodoText>, ModelOnly)>,
+    checked_changed: Query<(), (Changed<ModelTodoChecked>, ModelOnly)>,
+    mut removed: RemovedComponents<ModelTodoText>,
+    todos: Query<(&TodoId, &ModelTodoText, &ModelTodoChecked), ModelOnly>,
+) {
+    if text_changed.is_empty() && checked_changed.is_empty() && removed.is_empty() {
+        return;
+    }
+    removed.clear();
+
+    let mut todos = todos
+        .iter()
+        .map(|(id, text, checked)| persistence::PersistedTodo {
+            id: *id,
+            text: text.0.clone(),
+            checked: checked.0,
+        })
+        .collect::<Vec<_>>();
+    todos.sort_by_key(|todo| todo.id);
+    persistence::save(&todos);
+}
+
+/// Whenever a model is removed, views that depend on it are updated
+///
+/// Model -> View + Event<SetFocus>
+fn remove_displayed_todos(
+    mut removed: RemovedComponents<ModelTodoText>,
+    views: Query<(Entity, &View), (ViewOnly, With<markers::TodoRootView>)>,
+    mut commands: Commands,
+    mut set_focus: EventWriter<SetFocus>,
+) {
+    let models_to_views = views
+        .iter()
+        .map(|(entity, view)| (view.0, entity))
+        .collect::<std::collections::HashMap<_, _>>();
+    for model_entity in removed.iter() {
+        if let Some(view_entity) = models_to_views.get(&model_entity) {
+            commands.entity(*view_entity).despawn_recursive();
+            set_focus.send(SetFocus(None));
+        }
+    }
+}
+
+/// Counts consecutive presses on the same key, see [`ClickTracker::count`]
+///
+/// The plugin keeps one keyed by view `Entity`, fed from `Interaction` by `track_clicks`.
+/// Other widgets can read that one, or keep their own keyed by what they show, e.g. a [`TodoId`].
+#[derive(Resource)]
+pub struct ClickTracker<K = Entity> {
+    last_presses: std::collections::HashMap<K, Click>,
+}
+
+impl<K> Default for ClickTracker<K> {
+    fn default() -> Self {
+        Self {
+            last_presses: default(),
+        }
+    }
+}
+
+struct Click {
+    time: std::time::Duration,
+    position: Option<Vec2>,
+    count: u32,
+}
+
+impl<K: Copy + Eq + std::hash::Hash> ClickTracker<K> {
+    /// Presses further apart than this start a new count
+    const MAX_DELAY: std::time::Duration = std::time::Duration::from_millis(500);
+    /// Presses further apart than this (in logical pixels) start a new count
+    const MAX_DISTANCE: f32 = 4.;
+
+    /// Counts a press on `key` at `time`, `position` is left out of the comparison if unknown
+    pub fn press(&mut self, key: K, time: std::time::Duration, position: Option<Vec2>) {
+        let count = match self.last_presses.get(&key) {
+            Some(last)
+                if time - last.time <= Self::MAX_DELAY
+                    && match (last.position, position) {
+                        (Some(last), Some(current)) => last.distance(current) <= Self::MAX_DISTANCE,
+                        _ => true,
+                    } =>
+            {
+                last.count + 1
+            }
+            _ => 1,
+        };
+        self.last_presses.insert(
+            key,
+            Click {
+                time,
+                position,
+                count,
+            },
+        );
+    }
+
+    /// Drops the presses that are too old to be followed up on
+    pub fn forget_before(&mut self, now: std::time::Duration) {
+        self.last_presses
+            .retain(|_, click| now - click.time <= Self::MAX_DELAY);
+    }
+
+    /// The number of consecutive presses on `key`, with the latest one counted last
+    pub fn count(&self, key: K) -> u32 {
+        self.last_presses.get(&key).map_or(0, |click| click.count)
+    }
+
+    pub fn is_double_click(&self, key: K) -> bool {
+        self.count(key) >= 2
+    }
+}
+
+/// Hides a view unless one of the views of the same model is hovered
+#[derive(Component)]
+struct ShowOnHover;
+
+/// Colours the `Text` children of an `Interaction` view depending on whether it is hovered
+#[derive(Component)]
+struct HoverTextColor {
+    normal: Color,
+    hovered: Color,
+}
+
+/// Which part of the [`Theme`] a view is drawn with, see `restyle`
+#[derive(Component, Clone, Copy, Debug, PartialEq, Eq)]
+enum Styled {
+    Page,
+    App,
+    Button,
+    Title,
+    Footer,
+    List,
+    Row,
+    Deleter,
+    DeleterText,
+    Input,
+    TodoText,
+}
+
+/// Marker component to indicate that this entity is part of the Model
+///
+/// Mutually exclusive with [`View`]
+#[derive(Component)]
+pub struct Model;
+
+/// Marker component to indicate that this entity is part of the View
+///
+/// Mutually exclusive with [`Model`]
+///
+/// This currently also "tracks" the model entity
+#[derive(Component)]
+pub struct View(pub Entity);
+
+/// This type alias has the effect of marking a `Model` and not a `View`
+/// equivalent to `Marker::Model` for `enum Marker { Model, View }`
+pub type ModelOnly = (With<Model>, Without<View>);
+
+/// This type alias has the effect of marking a `View` and not a `Model`
+/// equivalent to `Marker::View` for `enum Marker { Model, View }`
+pub type ViewOnly = (Without<Model>, With<View>);
+
+/// Probably unnecessary type alias, documents the intent
+pub type ModelTodoEntity = Entity;
+
+/// Probably unnecessary type alias, documents the intent
+pub type ModelInputEntity = Entity;
+
+/// Probably unnecessary type alias, documents the intent
+pub type ModelFilterEntity = Entity;
+
+/// Combined with `ModelTodoText` and `ModelTodoChecked`,
+/// this is functionally equivalent to
+/// ```rs
+/// struct Todo {
+///     text: String,
+///     checked: bool,
+/// }
+///
+/// struct Todos(Vec<Todo>);
+///
+/// impl Todos {
+///     fn create(&mut self, text: String);
+///     fn delete(&mut self, idx: usize);
+///     fn update_checked(&mut self, idx: usize, checked: bool);
+///     fn toggle_checked(&mut self, idx: usize);
+///     fn update_text(&mut self, idx: usize, text: String);
+///     fn update_all_checked(&mut self, checked: bool);
+///     fn clear_completed(&mut self);
+/// }
+/// ```
+///
+/// Components are a stand-in for properties.
+/// Events are a stand-in for methods.
+/// Entities are a stand-in for references.
+#[derive(Event, Clone, Debug, serde::Serialize, serde::Deserialize)]
+pub enum ModelTodoAction {
+    /// The text is trimmed, and nothing is created if it is blank
+    Create(String),
+    /// Brings back a deleted todo, see [`History`]
+    Restore(TodoId, String, bool),
+    Delete(TodoId),
+    UpdateText(TodoId, String),
+    UpdateChecked(TodoId, bool),
+    ToggleChecked(TodoId),
+    /// Committing an edit trims the text, and deletes the todo if it is blank
+    Edit(TodoId, bool),
+    /// Leaves edit mode, reverting the text to what it was when the edit started
+    CancelEdit(TodoId),
+    UpdateAllChecked(bool),
+    ClearCompleted,
+}
+
+/// Identifies a todo across save/load and undo/redo, unlike its [`ModelTodoEntity`]
+///
+/// Both the model and its views carry it.
+#[derive(
+    Component,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    Debug,
+    serde::Serialize,
+    serde::Deserialize,
+)]
+pub struct TodoId(pub u64);
+
+/// Looks up the model entity of a [`TodoId`]
+#[derive(Resource, Default)]
+struct TodoIndex {
+    next: u64,
+    entities: std::collections::HashMap<TodoId, ModelTodoEntity>,
+}
+
+impl TodoIndex {
+    fn next_id(&mut self) -> TodoId {
+        let id = TodoId(self.next);
+        self.next += 1;
+        id
+    }
+
+    /// Makes sure `id` is never handed out by [`TodoIndex::next_id`]
+    fn reserve(&mut self, id: TodoId) {
+        self.next = self.next.max(id.0 + 1);
+    }
+
+    fn get(&self, id: TodoId) -> Option<ModelTodoEntity> {
+        self.entities.get(&id).copied()
+    }
+
+    fn insert(&mut self, id: TodoId, e: ModelTodoEntity) {
+        self.entities.insert(id, e);
+    }
+
+    fn remove(&mut self, id: TodoId) {
+        self.entities.remove(&id);
+    }
+}
+
+/// Undo and redo stacks of [`ModelTodoAction`]s
+///
+/// Every entry holds the actions that revert one action applied in `update_todo_model`
+#[derive(Resource, Default)]
+struct History {
+    undo: Vec<Vec<ModelTodoAction>>,
+    redo: Vec<Vec<ModelTodoAction>>,
+    /// The todo whose text updates are currently merged into a single entry
+    typing: Option<TodoId>,
+}
+
+impl History {
+    fn record(&mut self, action: &ModelTodoAction, inverse: Vec<ModelTodoAction>) {
+        // a cancelled edit leaves no trace, so what was typed during it is dropped too
+        if let ModelTodoAction::CancelEdit(id) = action {
+            if self.typing.take() == Some(*id) {
+                self.undo.pop();
+            }
+            return;
+        }
+        if let ModelTodoAction::Edit(id, false) = action {
+            // a todo deleted by committing it blank is restored with the text from before the edit,
+            // so what was typed during it is dropped too
+            let deleted = matches!(inverse[..], [ModelTodoAction::Restore(..)]);
+            if deleted && self.typing == Some(*id) {
+                self.undo.pop();
+            }
+        }
+        if let ModelTodoAction::Edit(..) = action {
+            self.typing = None;
+        }
+        if inverse.is_empty() {
+            return;
+        }
+        self.redo.clear();
+        // every keystroke is an update, only the text from before the first one is kept
+        if let ModelTodoAction::UpdateText(e, _) = action {
+            if self.typing == Some(*e) {
+                return;
+            }
+            self.typing = Some(*e);
+        } else {
+            self.typing = None;
+        }
+        self.undo.push(inverse);
+    }
+}
+
+/// Ctrl+Z / Ctrl+Shift+Z, see [`History`]
+#[derive(Event, Clone, Debug, serde::Serialize, serde::Deserialize)]
+enum HistoryAction {
+    Undo,
+    Redo,
+}
+
+/// See [`ModelTodoAction`].
+#[derive(Component)]
+pub struct ModelTodoText(pub String);
+
+/// See [`ModelTodoAction`].
+#[derive(Component)]
+pub struct ModelTodoChecked(pub bool);
+
+/// Holds the text from before the edit while the todo is being edited
+#[derive(Component)]
+pub struct ModelTodoEdit(Option<String>);
+
+impl ModelTodoEdit {
+    pub fn is_editing(&self) -> bool {
+        self.0.is_some()
+    }
+}
+
+/// See [`Derive`]
+#[derive(Component, PartialEq)]
+pub struct DerivedModel<T>(pub T);
+
+#[derive(Debug, PartialEq)]
+pub struct TodoActiveCount(pub usize);
+
+// ModelTodoChecked -> DerivedModel<TodoActiveCount>
+impl Derive for TodoActiveCount {
+    type Source = ModelTodoChecked;
+    type Contribution = usize;
+    type State = usize;
+
+    fn contribution(checked: &ModelTodoChecked) -> usize {
+        usize::from(!checked.0)
+    }
+
+    fn add(count: &mut usize, contribution: &usize) {
+        *count += contribution;
+    }
+
+    fn remove(count: &mut usize, contribution: &usize) {
+        *count -= contribution;
+    }
+
+    fn derive(count: &usize) -> Self {
+        Self(*count)
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct TodoCompletedCount(pub usize);
+
+// ModelTodoChecked -> DerivedModel<TodoCompletedCount>
+impl Derive for TodoCompletedCount {
+    type Source = ModelTodoChecked;
+    type Contribution = usize;
+    type State = usize;
+
+    fn contribution(checked: &ModelTodoChecked) -> usize {
+        usize::from(checked.0)
+    }
+
+    fn add(count: &mut usize, contribution: &usize) {
+        *count += contribution;
+    }
+
+    fn remove(count: &mut usize, contribution: &usize) {
+        *count -= contribution;
+    }
+
+    fn derive(count: &usize) -> Self {
+        Self(*count)
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct TodoTotalCount(pub usize);
+
+// ModelTodoChecked -> DerivedModel<TodoTotalCount>
+impl Derive for TodoTotalCount {
+    type Source = ModelTodoChecked;
+    type Contribution = ();
+    type State = usize;
+
+    fn contribution(_: &ModelTodoChecked) {}
+
+    fn add(count: &mut usize, _: &()) {
+        *count += 1;
+    }
+
+    fn remove(count: &mut usize, _: &()) {
+        *count -= 1;
+    }
+
+    fn derive(count: &usize) -> Self {
+        Self(*count)
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct TodoAllChecked(pub bool);
+
+/// The number of todos and how many of them are checked
+#[derive(Default)]
+pub struct TodoCheckedCounts {
+    total: usize,
+    checked: usize,
+}
+
+// ModelTodoChecked -> DerivedModel<TodoAllChecked>
+impl Derive for TodoAllChecked {
+    type Source = ModelTodoChecked;
+    type Contribution = bool;
+    type State = TodoCheckedCounts;
+
+    fn contribution(checked: &ModelTodoChecked) -> bool {
+        checked.0
+    }
+
+    fn add(counts: &mut TodoCheckedCounts, checked: &bool) {
+        counts.total += 1;
+        counts.checked += usize::from(*checked);
+    }
+
+    fn remove(counts: &mut TodoCheckedCounts, checked: &bool) {
+        counts.total -= 1;
+        counts.checked -= usize::from(*checked);
+    }
+
+    fn derive(counts: &TodoCheckedCounts) -> Self {
+        Self(counts.total > 0 && counts.checked == counts.total)
+    }
+}
+
+/// Combined with `ModelInputText`,
+/// this is functionally equivalent to
+/// ```rs
+/// struct Input {
+///     text: String,
+/// }
+///
+/// impl Todos {
+///     fn create(&mut self, text: String);
+///     fn update_text(&mut self, idx: usize, text: String);
+/// }
+/// ```
+///
+/// Components are a stand-in for properties.
+/// Events are a stand-in for methods.
+/// Entities are a stand-in for references.
+#[derive(Event, Clone, Debug)]
+pub enum ModelInputAction {
+    Create(String),
+    UpdateText(ModelInputEntity, String),
+    Edit(ModelInputEntity, bool),
+}
+
+/// See [`ModelInputAction`].
+#[derive(Component)]
+pub struct ModelInputText(pub String);
+
+#[derive(Component)]
+pub struct ModelInputEdit(pub bool);
+
+/// Combined with `ModelFilter`,
+/// this is functionally equivalent to
+/// ```rs
+/// struct Filter {
+///     selected: TodoFilter,
+/// }
+///
+/// impl Filter {
+///     fn select(&mut self, selected: TodoFilter);
+/// }
+/// ```
+///
+/// Components are a stand-in for properties.
+/// Events are a stand-in for methods.
+/// Entities are a stand-in for references.
+#[derive(Event, Clone, Debug)]
+pub enum ModelFilterAction {
+    Select(ModelFilterEntity, TodoFilter),
+}
+
+/// See [`ModelFilterAction`].
+#[derive(Component)]
+pub struct ModelFilter(pub TodoFilter);
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug, serde::Serialize, serde::Deserialize)]
+pub enum TodoFilter {
+    All,
+    Active,
+    Completed,
+}
+
+/// An action that was rejected instead of being applied to the model
+///
+/// Logged as a warning, and readable like any other event, e.g. by tests or tools.
+#[derive(Event, Debug)]
+pub struct ModelActionError {
+    pub action: RejectedAction,
+    pub reason: ModelActionErrorReason,
+}
+
+impl ModelActionError {
+    fn new(action: impl Into<RejectedAction>, reason: ModelActionErrorReason) -> Self {
+        Self {
+            action: action.into(),
+            reason,
+        }
+    }
+}
+
+/// The action of a [`ModelActionError`], as it was sent
+#[derive(Debug)]
+pub enum RejectedAction {
+    Todo(ModelTodoAction),
+    Input(ModelInputAction),
+    Filter(ModelFilterAction),
+}
+
+impl From<ModelTodoAction> for RejectedAction {
+    fn from(action: ModelTodoAction) -> Self {
+        Self::Todo(action)
+    }
+}
+
+impl From<ModelInputAction> for RejectedAction {
+    fn from(action: ModelInputAction) -> Self {
+        Self::Input(action)
+    }
+}
+
+impl From<ModelFilterAction> for RejectedAction {
+    fn from(action: ModelFilterAction) -> Self {
+        Self::Filter(action)
+    }
+}
+
+/// Why the action of a [`ModelActionError`] was rejected
+#[derive(Debug)]
+pub enum ModelActionErrorReason {
+    /// No todo has this id, e.g. it was deleted earlier in the same frame
+    UnknownTodo(TodoId),
+    /// A todo with this id already exists
+    DuplicateTodo(TodoId),
+    /// The entity is not (or no longer) a model of the right kind
+    MissingModel(Entity),
+}
+
+impl std::fmt::Display for ModelActionErrorReason {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::UnknownTodo(id) => write!(f, "no todo with id {}", id.0),
+            Self::DuplicateTodo(id) => write!(f, "a todo with id {} already exists", id.0),
+            Self::MissingModel(e) => write!(f, "{e:?} is not a model entity"),
+        }
+    }
+}
+
+/// https://todomvc.com/examples/vanillajs/node_modules/todomvc-app-css/index.css
+///
+/// ```css
+/// body {
+///     background: #f5f5f5;
+///     color: #4d4d4d;
+/// }
+/// .todoapp {
+///     background: #fff;
+///     box-shadow: 0 2px 4px 0 rgba(0, 0, 0, 0.2),
+///                 0 25px 50px 0 rgba(0, 0, 0, 0.1);
+/// }
+/// .todoapp input::input-placeholder {
+///     color: #e6e6e6;
+/// }
+/// .todoapp h1 {
+///     color: rgba(175, 47, 47, 0.15);
+/// }
+/// .new-todo {
+///     background: rgba(0, 0, 0, 0.003);
+///     box-shadow: inset 0 -2px 1px rgba(0,0,0,0.03);
+/// }
+/// .main {
+///     border-top: 1px solid #e6e6e6;
+/// }
+/// .toggle-all + label:before {
+///     color: #e6e6e6;
+/// }
+/// .toggle-all:checked + label:before {
+///     color: #737373;
+/// }
+/// .todo-list li {
+///     border-bottom: 1px solid #ededed;
+/// }
+/// .todo-list li.completed label {
+///     color: #d9d9d9;
+/// }
+/// .todo-list li .destroy {
+///     color: #cc9a9a;
+/// }
+/// .todo-list li .destroy:hover {
+///     color: #af5b5e;
+/// }
+/// .footer {
+///     color: #777;
+///     border-top: 1px solid #e6e6e6;
+/// }
+/// .footer:before {
+///     box-shadow: 0 1px 1px rgba(0, 0, 0, 0.2),
+///                 0 8px 0 -3px #f6f6f6,
+///                 0 9px 1px -3px rgba(0, 0, 0, 0.2),
+///                 0 16px 0 -6px #f6f6f6,
+///                 0 17px 2px -6px rgba(0, 0, 0, 0.2);
+/// }
+/// .info {
+///     color: #bfbfbf;
+///     text-shadow: 0 1px 0 rgba(255, 255, 255, 0.5);
+/// }
+/// .filters li a:hover {
+///     border-color: rgba(175, 47, 47, 0.1);
+/// }
+/// .filters li a.selected {
+///     border-color: rgba(175, 47, 47, 0.2);
+/// }
+/// ```
+mod theme {
+    use bevy::{
+        asset::{AssetLoader, Error, LoadContext, LoadedAsset},
+        prelude::*,
+        reflect::{TypePath, TypeUuid},
+        utils::BoxedFuture,
+    };
+    use serde::{Deserialize, Deserializer};
+
+    /// Colors, text styles and spacing of every view, light by default
+    ///
+    /// Replacing it at runtime restyles the views that are already spawned.
+    #[derive(Resource, Clone, Debug, PartialEq, Deserialize)]
+    pub struct Theme {
+        pub palette: Palette,
+        pub text: TextStyles,
+        pub spacing: Spacing,
+    }
+
+    /// Named after the css rules they come from
+    ///
+    /// In `.theme.ron` files colors are hex strings, e.g. `"#d9d9d9"` or `"#af2f2f33"`.
+    #[derive(Clone, Debug, PartialEq, Deserialize)]
+    pub struct Palette {
+        #[serde(deserialize_with = "hex_color")]
+        pub body_background: Color,
+        #[serde(deserialize_with = "hex_color")]
+        pub todoapp_background: Color,
+        #[serde(deserialize_with = "hex_color")]
+        pub main_border_top: Color,
+        #[serde(deserialize_with = "hex_color")]
+        pub todo_list_item_border_bottom: Color,
+        #[serde(deserialize_with = "hex_color")]
+        pub todo_list_item_completed_color: Color,
+        #[serde(deserialize_with = "hex_color")]
+        pub todo_list_item_destroy_color: Color,
+        #[serde(deserialize_with = "hex_color")]
+        pub todo_list_item_destroy_hover_color: Color,
+        #[serde(deserialize_with = "hex_color")]
+        pub filters_li_a_selected: Color,
+        #[serde(deserialize_with = "hex_color")]
+        pub edit_border: Color,
+    }
+
+    #[derive(Clone, Debug, PartialEq, Deserialize)]
+    pub struct TextStyles {
+        pub title: ThemeText,
+        pub footer: ThemeText,
+        pub todo: ThemeText,
+        pub placeholder: ThemeText,
+        pub checkmark: ThemeText,
+        pub checkmark_completed: ThemeText,
+        pub toggle_all: ThemeText,
+        pub toggle_all_checked: ThemeText,
+        pub destroy: ThemeText,
+    }
+
+    /// A `TextStyle` without its font, fonts live in [`crate::Fonts`]
+    #[derive(Clone, Copy, Debug, PartialEq, Deserialize)]
+    pub struct ThemeText {
+        pub font_size: f32,
+        #[serde(deserialize_with = "hex_color")]
+        pub color: Color,
+    }
+
+    #[derive(Clone, Copy, Debug, PartialEq, Deserialize)]
+    pub struct Spacing {
+        /// Left padding of the todo texts and the new todo input
+        pub text_padding: i32,
+    }
+
+    impl ThemeText {
+        pub fn with_font(self, font: Handle<Font>) -> TextStyle {
+            TextStyle {
+                font_size: self.font_size,
+                color: self.color,
+                font,
+            }
+        }
+    }
+
+    impl Default for Theme {
+        fn default() -> Self {
+            Self::light()
+        }
+    }
+
+    impl Theme {
+        /// Parses the contents of a `.theme.ron` file, see `assets/themes`
+        pub fn from_ron(source: &str) -> Result<Self, ron::error::SpannedError> {
+            ron::from_str(source)
+        }
+
+        /// The look of todomvc.com, `assets/themes/light.theme.ron`
+        pub fn light() -> Self {
+            Self::from_ron(include_str!("../assets/themes/light.theme.ron"))
+                .expect("bundled light theme")
+        }
+
+        /// `assets/themes/dark.theme.ron`
+        pub fn dark() -> Self {
+            Self::from_ron(include_str!("../assets/themes/dark.theme.ron"))
+                .expect("bundled dark theme")
+        }
+    }
+
+    /// A theme loaded from a `.theme.ron` file
+    #[derive(Debug, TypeUuid, TypePath)]
+    #[uuid = "c6a3e0d1-4b7f-4e29-8f15-93d2a7b64c08"]
+    pub struct ThemeAsset(pub Theme);
+
+    #[derive(Default)]
+    pub struct ThemeLoader;
+
+    impl AssetLoader for ThemeLoader {
+        fn load<'a>(
+            &'a self,
+            bytes: &'a [u8],
+            load_context: &'a mut LoadContext,
+        ) -> BoxedFuture<'a, Result<(), Error>> {
+            Box::pin(async move {
+                let theme = Theme::from_ron(std::str::from_utf8(bytes)?)?;
+                load_context.set_default_asset(LoadedAsset::new(ThemeAsset(theme)));
+                Ok(())
+            })
+        }
+
+        fn extensions(&self) -> &[&str] {
+            &["theme.ron"]
+        }
+    }
+
+    /// Handle to the theme file picked in [`crate::TodoMvcPlugin::theme`]
+    #[derive(Resource)]
+    pub struct ThemeSource(pub Handle<ThemeAsset>);
+
+    /// Every time the file is loaded or saved again, a file that fails to parse keeps the current theme
+    ///
+    /// Res<Assets<ThemeAsset>> -> Res<Theme>
+    pub fn apply_theme_asset(
+        mut events: EventReader<AssetEvent<ThemeAsset>>,
+        source: Res<ThemeSource>,
+        assets: Res<Assets<ThemeAsset>>,
+        mut theme: ResMut<Theme>,
+    ) {
+        for event in events.iter() {
+            match event {
+                AssetEvent::Created { handle } | AssetEvent::Modified { handle }
+                    if *handle == source.0 =>
+                {
+                    if let Some(asset) = assets.get(handle) {
+                        theme.set_if_neq(asset.0.clone());
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    fn hex_color<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Color, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        Color::hex(&s).map_err(|err| serde::de::Error::custom(format!("{s:?}: {err}")))
+    }
+}
+
+/// Todos are stored as JSON,
+/// in a file in the platform data dir on native and in `window.localStorage` on wasm
+pub mod persistence {
+    use bevy::log::warn;
+    use serde::{Deserialize, Serialize};
+
+    use crate::TodoId;
+
+    const KEY: &str = "todos-bevy";
+
+    #[derive(Serialize, Deserialize, Clone)]
+    pub struct PersistedTodo {
+        pub id: TodoId,
+        pub text: String,
+        pub checked: bool,
+    }
+
+    /// A todo as read back from storage,
+    /// todos saved before todos had ids are stored as just `{"text": .., "checked": ..}`
+    #[derive(Deserialize, Debug, PartialEq)]
+    pub struct StoredTodo {
+        pub id: Option<TodoId>,
+        pub text: String,
+        pub checked: bool,
+    }
+
+    pub fn load() -> Vec<StoredTodo> {
+        read().map(|json| parse(&json)).unwrap_or_default()
+    }
+
+    pub fn parse(json: &str) -> Vec<StoredTodo> {
+        serde_json::from_str(json).unwrap_or_else(|err| {
+            warn!("discarding stored todos: {err}");
+            Vec::new()
+        })
+    }
+
+    pub fn save(todos: &[PersistedTodo]) {
+        match serde_json::to_string(todos) {
+            Ok(json) => write(&json),
+            Err(err) => warn!("failed to serialize todos: {err}"),
+        }
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    fn path() -> Option<std::path::PathBuf> {
+        Some(
+            dirs::data_dir()?
+                .join("bevy-todomvc")
+                .join(format!("{KEY}.json")),
+        )
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    fn read() -> Option<String> {
+        std::fs::read_to_string(path()?).ok()
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    fn write(json: &str) {
+        let Some(path) = path() else {
+            warn!("no data dir to store todos in");
+            return;
+        };
+        let written = path
+            .parent()
+            .map_or(Ok(()), std::fs::create_dir_all)
+            .and_then(|_| std::fs::write(&path, json));
+        if let Err(err) = written {
+            warn!("failed to write todos to {}: {err}", path.display());
+        }
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    fn local_storage_call(
+        method: &str,
+        args: &[&str],
+    ) -> Result<wasm_bindgen::JsValue, wasm_bindgen::JsValue> {
+        use js_sys::{Array, Function, Reflect};
+        use wasm_bindgen::{JsCast, JsValue};
+
+        let storage = Reflect::get(&js_sys::global(), &JsValue::from_str("localStorage"))?;
+        let method: Function = Reflect::get(&storage, &JsValue::from_str(method))?.dyn_into()?;
+        let args = args
+            .iter()
+            .map(|arg| JsValue::from_str(arg))
+            .collect::<Array>();
+        method.apply(&storage, &args)
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    fn read() -> Option<String> {
+        local_storage_call("getItem", &[KEY]).ok()?.as_string()
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    fn write(json: &str) {
+        if let Err(err) = local_storage_call("setItem", &[KEY, json]) {
+            warn!("failed to write todos to localStorage: {err:?}");
+        }
+    }
+}
+
+mod l10n {
+    use bevy::{
+        asset::{AssetLoader, Error, LoadContext, LoadedAsset},
+        ecs::system::SystemParam,
+        prelude::*,
+        reflect::{TypePath, TypeUuid},
+        utils::{BoxedFuture, HashMap},
+    };
+
+    /// Languages that have a catalogue in `assets/locales`, the first one is the fallback
+    const LANGUAGES: &[&str] = &["en-US", "de-DE", "fr-FR"];
+
+    /// Language all user-visible strings are displayed in
+    #[derive(Resource, Debug, Clone, PartialEq, Eq)]
+    pub struct Locale(&'static str);
+
+    impl Locale {
+        /// Picks the closest available language, e.g. `de_AT.UTF-8` -> `de-DE`
+        pub fn new(language: &str) -> Self {
+            let language = language
+                .split('.')
+                .next()
+                .unwrap_or_default()
+                .replace('_', "-");
+            let primary = language.split('-').next().unwrap_or_default();
+            let found = LANGUAGES
+                .iter()
+                .find(|available| available.eq_ignore_ascii_case(&language))
+                .or_else(|| {
+                    LANGUAGES.iter().find(|available| {
+                        available
+                            .split('-')
+                            .next()
+                            .is_some_and(|p| p.eq_ignore_ascii_case(primary))
+                    })
+                });
+            Self(found.copied().unwrap_or(LANGUAGES[0]))
+        }
+
+        pub fn language(&self) -> &'static str {
+            self.0
+        }
+    }
+
+    impl Default for Locale {
+        fn default() -> Self {
+            Self::new(&system_language().unwrap_or_default())
+        }
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    fn system_language() -> Option<String> {
+        ["LC_ALL", "LC_MESSAGES", "LANG"]
+            .into_iter()
+            .find_map(|var| std::env::var(var).ok().filter(|v| !v.is_empty()))
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    fn system_language() -> Option<String> {
+        use js_sys::Reflect;
+        use wasm_bindgen::JsValue;
+
+        let navigator = Reflect::get(&js_sys::global(), &JsValue::from_str("navigator")).ok()?;
+        Reflect::get(&navigator, &JsValue::from_str("language"))
+            .ok()?
+            .as_string()
+    }
+
+    /// Messages of one language, parsed from a `.lang` file:
+    ///
+    /// ```text
+    /// # comment
+    /// filter-all = All
+    /// items-left[one] = {count} item left
+    /// items-left[other] = {count} items left
+    /// ```
+    #[derive(Debug, TypeUuid, TypePath)]
+    #[uuid = "5a3f0c8e-7d52-4f0b-9a61-2c1e4b8d7f93"]
+    pub struct MessageCatalogue {
+        messages: HashMap<String, String>,
+    }
+
+    impl MessageCatalogue {
+        pub fn parse(source: &str) -> Result<Self, Error> {
+            let mut messages = HashMap::new();
+            for (number, line) in source.lines().enumerate() {
+                let line = line.trim();
+                if line.is_empty() || line.starts_with('#') {
+                    continue;
+                }
+                let Some((key, message)) = line.split_once('=') else {
+                    return Err(Error::msg(format!(
+                        "line {}: expected `key = message`",
+                        number + 1
+                    )));
+                };
+                messages.insert(key.trim().to_string(), message.trim().to_string());
+            }
+            Ok(Self { messages })
+        }
+
+        fn get(&self, key: &str) -> Option<&str> {
+            self.messages.get(key).map(String::as_str)
+        }
+    }
+
+    #[derive(Default)]
+    pub struct MessageCatalogueLoader;
+
+    impl AssetLoader for MessageCatalogueLoader {
+        fn load<'a>(
+            &'a self,
+            bytes: &'a [u8],
+            load_context: &'a mut LoadContext,
+        ) -> BoxedFuture<'a, Result<(), Error>> {
+            Box::pin(async move {
+                let catalogue = MessageCatalogue::parse(std::str::from_utf8(bytes)?)?;
+                load_context.set_default_asset(LoadedAsset::new(catalogue));
+                Ok(())
+            })
+        }
+
+        fn extensions(&self) -> &[&str] {
+            &["lang"]
+        }
+    }
+
+    /// Handles to the catalogues requested so far, by language
+    #[derive(Resource, Default)]
+    pub struct MessageCatalogues(HashMap<&'static str, Handle<MessageCatalogue>>);
+
+    /// Res<Locale> -> Res<MessageCatalogues>
+    pub fn load_catalogues(
+        locale: Res<Locale>,
+        asset_server: Res<AssetServer>,
+        mut catalogues: ResMut<MessageCatalogues>,
+    ) {
+        if !locale.is_changed() {
+            return;
+        }
+        for language in [LANGUAGES[0], locale.language()] {
+            catalogues
+                .0
+                .entry(language)
+                .or_insert_with(|| asset_server.load(format!("locales/{language}.lang")));
+        }
+    }
+
+    /// CLDR plural category of `count`, used as the `[category]` suffix of plural keys
+    pub fn plural_category(language: &str, count: usize) -> &'static str {
+        match language.split('-').next().unwrap_or_default() {
+            "ja" | "ko" | "zh" => "other",
+            "fr" => match count {
+                0 | 1 => "one",
+                _ => "other",
+            },
+            "ru" | "uk" => match (count % 10, count % 100) {
+                (1, n) if n != 11 => "one",
+                (2..=4, n) if !(12..=14).contains(&n) => "few",
+                _ => "many",
+            },
+            _ => match count {
+                1 => "one",
+                _ => "other",
+            },
+        }
+    }
+
+    /// A view text resolved through the catalogue of the current `Locale`
+    #[derive(Component, Debug, Clone)]
+    pub struct Localized {
+        pub key: &'static str,
+        pub count: Option<usize>,
+    }
+
+    impl Localized {
+        pub fn new(key: &'static str) -> Self {
+            Self { key, count: None }
+        }
+
+        pub fn plural(key: &'static str, count: usize) -> Self {
+            Self {
+                key,
+                count: Some(count),
+            }
+        }
+    }
+
+    #[derive(SystemParam)]
+    pub struct Localizer<'w, 's> {
+        locale: Res<'w, Locale>,
+        catalogues: Res<'w, MessageCatalogues>,
+        assets: Res<'w, Assets<MessageCatalogue>>,
+        catalogue_events: EventReader<'w, 's, AssetEvent<MessageCatalogue>>,
+    }
+
+    impl<'w, 's> Localizer<'w, 's> {
+        /// Whether texts resolved earlier may be stale, consumes the pending catalogue events
+        pub fn is_changed(&mut self) -> bool {
+            let reloaded = self.catalogue_events.iter().count() > 0;
+            self.locale.is_changed() || reloaded
+        }
+
+        /// Looks the message up in the current language, then in the fallback language
+        ///
+        /// Returns `None` until a catalogue containing the key is loaded.
+        pub fn localize(&self, localized: &Localized) -> Option<String> {
+            let key = match localized.count {
+                Some(count) => format!(
+                    "{}[{}]",
+                    localized.key,
+                    plural_category(self.locale.language(), count)
+                ),
+                None => localized.key.to_string(),
+            };
+            let message = [self.locale.language(), LANGUAGES[0]]
+                .into_iter()
+                .filter_map(|language| self.assets.get(self.catalogues.0.get(language)?))
+                .find_map(|catalogue| {
+                    catalogue
+                        .get(&key)
+                        .or_else(|| catalogue.get(&format!("{}[other]", localized.key)))
+                })?;
+            Some(match localized.count {
+                Some(count) => message.replace("{count}", &count.to_string()),
+                None => message.to_string(),
+            })
+        }
+    }
+}
+
+mod routing {
+    use bevy::prelude::*;
+
+    use crate::TodoFilter;
+
+    /// The filter as it appears in the URL, `#/`, `#/active` or `#/completed`
+    ///
+    /// On wasm it mirrors `window.location.hash`, natively it is read from `--route`.
+    #[derive(Resource, Clone, Copy, PartialEq, Eq, Debug)]
+    pub struct Route(pub TodoFilter);
+
+    impl Route {
+        /// Accepts `#/active`, `/active` and `active`, anything unknown is `None`
+        pub fn parse(route: &str) -> Option<Self> {
+            match route.trim_start_matches('#').trim_start_matches('/') {
+                "" => Some(Self(TodoFilter::All)),
+                "active" => Some(Self(TodoFilter::Active)),
+                "completed" => Some(Self(TodoFilter::Completed)),
+                _ => None,
+            }
+        }
+    }
+
+    impl std::fmt::Display for Route {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            f.write_str(match self.0 {
+                TodoFilter::All => "#/",
+                TodoFilter::Active => "#/active",
+                TodoFilter::Completed => "#/completed",
+            })
+        }
+    }
+
+    impl Default for Route {
+        fn default() -> Self {
+            initial()
+                .and_then(|route| {
+                    let parsed = Self::parse(&route);
+                    if parsed.is_none() {
+                        warn!("ignoring unknown route {route:?}");
+                    }
+                    parsed
+                })
+                .unwrap_or(Self(TodoFilter::All))
+        }
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    fn initial() -> Option<String> {
+        crate::command_line_arg("--route")
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    fn initial() -> Option<String> {
+        location_hash()
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    fn location() -> Option<wasm_bindgen::JsValue> {
+        use js_sys::Reflect;
+        use wasm_bindgen::JsValue;
+
+        Reflect::get(&js_sys::global(), &JsValue::from_str("location")).ok()
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    fn location_hash() -> Option<String> {
+        use js_sys::Reflect;
+        use wasm_bindgen::JsValue;
+
+        Reflect::get(&location()?, &JsValue::from_str("hash"))
+            .ok()?
+            .as_string()
+    }
+
+    /// Hashes seen by the `hashchange` listener, drained every frame
+    #[cfg(target_arch = "wasm32")]
+    #[derive(Resource, Default, Clone)]
+    pub struct HashChanges(std::sync::Arc<std::sync::Mutex<Vec<String>>>);
+
+    /// Startup: registers the `hashchange` listener feeding [`HashChanges`]
+    #[cfg(target_arch = "wasm32")]
+    pub fn listen_hash_changes(mut commands: Commands) {
+        use js_sys::{Function, Reflect};
+        use wasm_bindgen::{closure::Closure, JsCast, JsValue};
+
+        let changes = HashChanges::default();
+        let pending = changes.0.clone();
+        let listener = Closure::<dyn FnMut()>::new(move || {
+            if let (Some(hash), Ok(mut pending)) = (location_hash(), pending.lock()) {
+                pending.push(hash);
+            }
+        });
+        let global = js_sys::global();
+        let added = Reflect::get(&global, &JsValue::from_str("addEventListener"))
+            .and_then(|add| add.dyn_into::<Function>())
+            .and_then(|add| {
+                add.call2(
+                    &global,
+                    &JsValue::from_str("hashchange"),
+                    listener.as_ref().unchecked_ref(),
+                )
+            });
+        match added {
+            // the listener lives as long as the page
+            Ok(_) => listener.forget(),
+            Err(err) => warn!("failed to listen to hashchange: {err:?}"),
+        }
+        commands.insert_resource(changes);
+    }
+
+    /// Event(hashchange) -> Res<Route>
+    #[cfg(target_arch = "wasm32")]
+    pub fn handle_hash_change(changes: Res<HashChanges>, mut route: ResMut<Route>) {
+        let Ok(mut pending) = changes.0.lock() else {
+            return;
+        };
+        for hash in pending.drain(..) {
+            match Route::parse(&hash) {
+                Some(parsed) => {
+                    route.set_if_neq(parsed);
+                }
+                None => warn!("ignoring unknown route {hash:?}"),
+            }
+        }
+    }
+
+    /// Res<Route> -> window.location.hash
+    #[cfg(target_arch = "wasm32")]
+    pub fn update_location_hash(route: Res<Route>) {
+        use js_sys::Reflect;
+        use wasm_bindgen::JsValue;
+
+        let hash = route.to_string();
+        if !route.is_changed() || location_hash().as_deref() == Some(hash.as_str()) {
+            return;
+        }
+        let Some(location) = location() else {
+            return;
+        };
+        if let Err(err) = Reflect::set(
+            &location,
+            &JsValue::from_str("hash"),
+            &JsValue::from_str(&hash),
+        ) {
+            warn!("failed to update location.hash: {err:?}");
+        }
+    }
+}
+
+/// Model -> View systems from just their inner logic, see [`AppBindExt::bind`] and [`AppBindExt::bind_themed`]
+///
+/// Every view carries its model entity in [`View`](crate::View), so the bound component is looked up per view.
+mod binding {
+    use bevy::prelude::*;
+
+    use crate::{ModelOnly, Theme, TodoMvcViews, View, ViewOnly};
+
+    pub trait AppBindExt {
+        /// Keeps the `V` of every view marked with `Marker` up to date with the `M` of its model
+        ///
+        /// ```ignore
+        /// app.bind::<ModelTodoText, CosmicText, markers::TodoTextView>(|text, view| {
+        ///     *view = CosmicText::OneStyle(text.0.clone());
+        /// });
+        /// ```
+        ///
+        /// M -> View
+        fn bind<M: Component, V: Component, Marker: Component>(
+            &mut self,
+            update: impl Fn(&M, &mut V) + Send + Sync + 'static,
+        ) -> &mut Self;
+
+        /// Like [`AppBindExt::bind`], for views whose look also depends on the [`Theme`],
+        /// they are updated again whenever the theme changes
+        ///
+        /// M + Res<Theme> -> View
+        fn bind_themed<M: Component, V: Component, Marker: Component>(
+            &mut self,
+            update: impl Fn(&M, &mut V, &Theme) + Send + Sync + 'static,
+        ) -> &mut Self;
+    }
+
+    impl AppBindExt for App {
+        fn bind<M: Component, V: Component, Marker: Component>(
+            &mut self,
+            update: impl Fn(&M, &mut V) + Send + Sync + 'static,
+        ) -> &mut Self {
+            let system =
+                move |models: Query<&M, (Changed<M>, ModelOnly)>,
+                      mut views: Query<(&mut V, &View), (With<Marker>, ViewOnly)>| {
+                    // outer loop, library-provided
+                    for (mut v, view) in views.iter_mut() {
+                        if let Ok(m) = models.get(view.0) {
+                            // inner logic, user-provided
+                            update(m, &mut v);
+                        }
+                    }
+                };
+            self.add_systems(Update, system.in_set(TodoMvcViews))
+        }
+
+        fn bind_themed<M: Component, V: Component, Marker: Component>(
+            &mut self,
+            update: impl Fn(&M, &mut V, &Theme) + Send + Sync + 'static,
+        ) -> &mut Self {
+            let system = move |models: Query<Ref<M>, ModelOnly>,
+                               mut views: Query<(&mut V, &View), (With<Marker>, ViewOnly)>,
+                               theme: Res<Theme>| {
+                // outer loop, library-provided
+                for (mut v, view) in views.iter_mut() {
+                    if let Ok(m) = models.get(view.0) {
+                        if !m.is_changed() && !theme.is_changed() {
+                            continue;
+                        }
+                        // inner logic, user-provided
+                        update(&m, &mut v, &theme);
+                    }
+                }
+            };
+            self.add_systems(Update, system.in_set(TodoMvcViews))
+        }
+    }
+}
+
+/// Derived models from just a reducer, see [`AppDeriveExt::derive`]
+///
+/// Every model's contribution is cached,
+/// so when a source changes or goes away only that contribution is taken out and put back in,
+/// instead of scanning every model again.
+mod derived {
+    use std::collections::HashMap;
+
+    use bevy::prelude::*;
+
+    use crate::{DerivedModel, ModelOnly, TodoMvcDerivedModels};
+
+    /// A value reduced from the `Source` component of every model
+    pub trait Derive: PartialEq + Send + Sync + 'static {
+        /// The component this is derived from, changes to anything else are not seen
+        type Source: Component;
+        /// What a single model adds to the state
+        type Contribution: Send + Sync + 'static;
+        /// What is kept between frames, e.g. a count or a sum
+        type State: Default + Send + Sync + 'static;
+
+        fn contribution(source: &Self::Source) -> Self::Contribution;
+        fn add(state: &mut Self::State, contribution: &Self::Contribution);
+        /// Takes back out a contribution that was added before
+        fn remove(state: &mut Self::State, contribution: &Self::Contribution);
+        fn derive(state: &Self::State) -> Self;
+    }
+
+    pub trait AppDeriveExt {
+        /// Keeps every `DerivedModel<T>` model up to date with the `T::Source` of all models,
+        /// only running the reducer for sources that were added, changed or removed
+        ///
+        /// T::Source -> DerivedModel<T>
+        fn derive<T: Derive>(&mut self) -> &mut Self;
+    }
+
+    impl AppDeriveExt for App {
+        fn derive<T: Derive>(&mut self) -> &mut Self {
+            self.add_systems(Update, update_derived::<T>.in_set(TodoMvcDerivedModels))
+        }
+    }
+
+    struct Contributions<T: Derive> {
+        by_model: HashMap<Entity, T::Contribution>,
+        state: T::State,
+    }
+
+    impl<T: Derive> Default for Contributions<T> {
+        fn default() -> Self {
+            Self {
+                by_model: HashMap::new(),
+                state: T::State::default(),
+            }
+        }
+    }
+
+    fn update_derived<T: Derive>(
+        mut contributions: Local<Contributions<T>>,
+        mut removed: RemovedComponents<T::Source>,
+        changed: Query<(Entity, &T::Source), (Changed<T::Source>, ModelOnly)>,
+        added: Query<(), (Added<DerivedModel<T>>, ModelOnly)>,
+        mut derived: Query<&mut DerivedModel<T>, ModelOnly>,
+    ) {
+        let Contributions { by_model, state } = &mut *contributions;
+        let mut is_changed = !added.is_empty();
+
+        for entity in removed.iter() {
+            if let Some(contribution) = by_model.remove(&entity) {
+                T::remove(state, &contribution);
+                is_changed = true;
+            }
+        }
+        for (entity, source) in changed.iter() {
+            let contribution = T::contribution(source);
+            T::add(state, &contribution);
+            if let Some(previous) = by_model.insert(entity, contribution) {
+                T::remove(state, &previous);
+            }
+            is_changed = true;
+        }
+
+        if !is_changed {
+            return;
+        }
+        for mut derived_model in derived.iter_mut() {
+            derived_model.set_if_neq(DerivedModel(T::derive(state)));
+        }
+    }
+}
+
+/// Recordings of what input handling did, one RON value per line:
+/// a [`Header`](recording::Header) with the todos at startup,
+/// then a [`Frame`](recording::Frame) for every frame in which something happened
+///
+/// Entities differ between runs, so actions refer to todos by `TodoId`
+/// and to the (single) input and filter models implicitly.
+/// Focus is not recorded, it follows from the replayed models like it does live,
+/// e.g. the input is focused on startup and an edited todo when it is displayed.
+mod recording {
+    use std::{
+        fs::File,
+        io::{BufWriter, Write},
+        path::Path,
+    };
+
+    use bevy::{
+        ecs::event::ManualEventReader,
+        input::{keyboard::KeyboardInput, mouse::MouseButtonInput},
+        prelude::*,
+        window::CursorMoved,
+    };
+    use serde::{Deserialize, Serialize};
+
+    use crate::{
+        persistence::PersistedTodo, HistoryAction, ModelFilterAction, ModelInputAction,
+        ModelTodoAction, TodoFilter,
+    };
+
+    #[derive(Serialize, Deserialize, Clone, Default)]
+    pub struct Header {
+        pub todos: Vec<PersistedTodo>,
+    }
+
+    #[derive(Serialize, Deserialize, Clone, Default)]
+    pub struct Frame {
+        /// Counted from the first `PreUpdate`
+        pub frame: u64,
+        /// Only for reading along, replay sends the actions below instead,
+        /// otherwise text typed into cosmic-edit would be applied a second time
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        pub raw: Vec<RawInput>,
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        pub todo: Vec<ModelTodoAction>,
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        pub input: Vec<InputAction>,
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        pub filter: Vec<TodoFilter>,
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        pub history: Vec<HistoryAction>,
+    }
+
+    impl Frame {
+        fn is_empty(&self) -> bool {
+            self.raw.is_empty()
+                && self.todo.is_empty()
+                && self.input.is_empty()
+                && self.filter.is_empty()
+                && self.history.is_empty()
+        }
+    }
+
+    /// Keyboard and mouse events as received from the window
+    #[derive(Serialize, Deserialize, Clone, Debug)]
+    pub enum RawInput {
+        Key {
+            scan_code: u32,
+            key_code: Option<KeyCode>,
+            pressed: bool,
+        },
+        MouseButton {
+            button: MouseButton,
+            pressed: bool,
+        },
+        Cursor(Vec2),
+    }
+
+    /// [`ModelInputAction`] without the entity of the input model
+    #[derive(Serialize, Deserialize, Clone, Debug)]
+    pub enum InputAction {
+        Create(String),
+        UpdateText(String),
+        Edit(bool),
+    }
+
+    impl From<&ModelInputAction> for InputAction {
+        fn from(action: &ModelInputAction) -> Self {
+            match action {
+                ModelInputAction::Create(text) => Self::Create(text.clone()),
+                ModelInputAction::UpdateText(_, text) => Self::UpdateText(text.clone()),
+                ModelInputAction::Edit(_, edit) => Self::Edit(*edit),
+            }
+        }
+    }
+
+    impl InputAction {
+        pub fn with_model(&self, e: Entity) -> ModelInputAction {
+            match self {
+                Self::Create(text) => ModelInputAction::Create(text.clone()),
+                Self::UpdateText(text) => ModelInputAction::UpdateText(e, text.clone()),
+                Self::Edit(edit) => ModelInputAction::Edit(e, *edit),
+            }
+        }
+    }
+
+    /// A recording read back for replay, see [`TodoMvcRecording::Replay`](crate::TodoMvcRecording)
+    #[derive(Clone, Default)]
+    pub struct Recording {
+        header: Header,
+        frames: Vec<Frame>,
+    }
+
+    impl Recording {
+        pub fn parse(source: &str) -> Result<Self, ron::error::SpannedError> {
+            let mut lines = source.lines().filter(|line| !line.trim().is_empty());
+            let header = match lines.next() {
+                Some(line) => ron::from_str(line)?,
+                None => Header::default(),
+            };
+            let frames = lines.map(ron::from_str).collect::<Result<_, _>>()?;
+            Ok(Self { header, frames })
+        }
+
+        pub fn load(path: impl AsRef<Path>) -> Result<Self, Box<dyn std::error::Error>> {
+            Ok(Self::parse(&std::fs::read_to_string(path)?)?)
+        }
+    }
+
+    /// Feeds a [`Recording`] back, one frame per `PreUpdate`
+    #[derive(Resource)]
+    pub struct Replay {
+        recording: Recording,
+        frame: u64,
+        next: usize,
+    }
+
+    impl Replay {
+        pub fn new(recording: Recording) -> Self {
+            Self {
+                recording,
+                frame: 0,
+                next: 0,
+            }
+        }
+
+        pub fn todos(&self) -> &[PersistedTodo] {
+            &self.recording.header.todos
+        }
+
+        /// Live input takes over once everything was replayed
+        pub fn is_finished(&self) -> bool {
+            self.next >= self.recording.frames.len()
+        }
+
+        /// The recorded frame for the current frame, if anything happened in it
+        pub fn current(&self) -> Option<&Frame> {
+            self.recording
+                .frames
+                .get(self.next)
+                .filter(|frame| frame.frame == self.frame)
+        }
+
+        pub fn advance(&mut self) {
+            if self.current().is_some() {
+                self.next += 1;
+            }
+            self.frame += 1;
+        }
+    }
+
+    /// Writes a recording while the app runs
+    ///
+    /// The readers are shared by the systems before and after input handling,
+    /// so only the events sent by input handling end up in a frame.
+    #[derive(Resource)]
+    pub struct Recorder {
+        file: Option<BufWriter<File>>,
+        frame: u64,
+        pub todo: ManualEventReader<ModelTodoAction>,
+        pub input: ManualEventReader<ModelInputAction>,
+        pub filter: ManualEventReader<ModelFilterAction>,
+        pub history: ManualEventReader<HistoryAction>,
+        pub keyboard: ManualEventReader<KeyboardInput>,
+        pub mouse_button: ManualEventReader<MouseButtonInput>,
+        pub cursor: ManualEventReader<CursorMoved>,
+    }
+
+    impl Recorder {
+        pub fn create(path: &Path) -> Self {
+            let file = File::create(path)
+                .map(BufWriter::new)
+                .map_err(|err| warn!("not recording to {}: {err}", path.display()))
+                .ok();
+            Self {
+                file,
+                frame: 0,
+                todo: default(),
+                input: default(),
+                filter: default(),
+                history: default(),
+                keyboard: default(),
+                mouse_button: default(),
+                cursor: default(),
+            }
+        }
+
+        pub fn write_header(&mut self, header: &Header) {
+            self.write(header);
+        }
+
+        /// Writes the current frame unless nothing happened in it
+        pub fn record(&mut self, mut frame: Frame) {
+            frame.frame = self.frame;
+            if !frame.is_empty() {
+                self.write(&frame);
+            }
+            self.frame += 1;
+        }
+
+        /// Every line is flushed, so a recording survives a panic in the frame after it
+        fn write(&mut self, value: &impl Serialize) {
+            let Some(file) = &mut self.file else {
+                return;
+            };
+            let written = ron::to_string(value)
+                .map_err(|err| err.to_string())
+                .and_then(|line| {
+                    writeln!(file, "{line}")
+                        .and_then(|_| file.flush())
+                        .map_err(|err| err.to_string())
+                });
+            if let Err(err) = written {
+                warn!("stopped recording: {err}");
+                self.file = None;
+            }
+        }
+    }
+}
+
+pub mod markers {
+    use bevy::prelude::Component;
+
+    #[derive(Component)]
+    pub struct TodoInputContainer;
+
+    #[derive(Component)]
+    pub struct TodoList;
+
+    #[derive(Component)]
+    pub struct TodoInput;
+
+    #[derive(Component)]
+    pub struct TodoToggleAll;
+
+    #[derive(Component)]
+    pub struct TodoRootView;
+
+    #[derive(Component)]
+    pub struct TodoTextView;
+
+    #[derive(Component)]
+    pub struct TodoCheckmarkView;
+
+    #[derive(Component)]
+    pub struct TodoDeleterView;
+
+    #[derive(Component)]
+    pub struct TodoFooter;
+
+    #[derive(Component)]
+    pub struct TodoItemsLeftView;
+
+    #[derive(Component)]
+    pub struct TodoFilters;
+
+    #[derive(Component)]
+    pub struct TodoClearCompleted;
+
+    #[derive(Component)]
+    pub struct TodoFilterAll;
+
+    #[derive(Component)]
+    pub struct TodoFilterActive;
+
+    #[derive(Component)]
+    pub struct TodoFilterCompleted;
+}
+
+/// Entity hierarchies described as nested tuples of entities or bundles,
+/// see `EntityTreeExt::tree` and `node`
+pub mod tree_builder {
+    /// TODO: Simplify the implementation (less re-implementation, call methods and functions instead)
+    /// TODO: #[inline]
+    use std::collections::HashMap;
+
+    use bevy::{
+        ecs::system::EntityCommands,
+        prelude::{
+            BuildChildren, Bundle, Children, Commands, Component, DespawnRecursiveExt, Entity,
+            Query,
+        },
+    };
+
+    /// Construct a [`Tree`] from any number of branches, e.g. a `Vec`
+    pub fn x<T>(root: Entity, branches: impl IntoIterator<Item = T>) -> Tree
+    where
+        T: IntoTree,
+    {
+        // take the current tree and create a new one
+        Tree::new(root, branches)
+    }
+
+    /// Construct a [`Tree`] without branches
+    pub fn l(leaf: Entity) -> Tree {
+        Tree::new_leaf(leaf)
+    }
+
+    /// Convert a tuple, or anything else that can be children, to a `TreeIterator`
+    pub fn c<T, S, I>(children: T) -> TreeIterator<I>
+    where
+        T: IntoTreeIterator<IterableStorage = S>,
+        S: IntoIterator<IntoIter = I>,
+        I: Iterator<Item = Tree>,
+    {
+        children.into_tree_iter()
+    }
+
+    /// Convert an `IntoIterator` of anything that can be converted into a tree,
+    /// e.g. a `HashSet` of entities, to a `TreeIterator`
+    ///
+    /// Runtime-sized, unlike tuples which stop at 16 siblings.
+    /// Common iterators, e.g. a `map` over bundles, are children as they are.
+    pub fn col<T>(children: impl IntoIterator<Item = T>) -> TreeIterator<std::vec::IntoIter<Tree>>
+    where
+        T: IntoTree,
+    {
+        TreeIterator::new(
+            children
+                .into_iter()
+                .map(IntoTree::into_tree)
+                .collect::<Vec<_>>(),
+        )
+    }
+
+    /// Construct a [`Tree`] of entities
+    fn build_tree<T, S, I>(root: Entity, children: T) -> Tree
+    where
+        // T is the thing that becomes an iterator over `Tree`s, e.g. `(Entity, Entity)`
+        T: IntoTreeIterator<IterableStorage = S>,
+        // S is the storage for the iterator, which becomes an iterator over `Tree`s, e.g. `[Entity, Entity]`
+        S: IntoIterator<IntoIter = I>,
+        // I is the iterator over `Tree`s
+        I: Iterator<Item = Tree>,
+    {
+        // take the current tree and create a new one
+        let branches = children.into_tree_iter();
+        Tree::new(root, branches)
+    }
+
+    pub trait IteratorAdapter {
+        /// Construct a `TreeIterator` from an `Iterator` of anything that can be converted into a tree,
+        /// see [`col`]
+        fn c(self) -> TreeIterator<std::vec::IntoIter<Tree>>;
+    }
+
+    impl<S> IteratorAdapter for S
+    where
+        S: IntoIterator,
+        S::Item: IntoTree,
+    {
+        fn c(self) -> TreeIterator<std::vec::IntoIter<Tree>> {
+            col(self)
+        }
+    }
+
+    type Apply = Box<dyn FnOnce(&mut EntityCommands)>;
+
+    /// The root of a [`Tree`], either already spawned or spawned when the tree is built
+    enum Root {
+        Entity(Entity),
+        Bundle {
+            /// Only applied when the entity is spawned
+            spawn: Apply,
+            /// Applied whether the entity is spawned or reused by [`reconcile`]
+            always: Vec<Apply>,
+            label: Option<&'static str>,
+        },
+    }
+
+    /// A type that simply stores the root, and the trees of all branches, recursively
+    pub struct Tree {
+        root: Root,
+        branches: Vec<Tree>,
+    }
+
+    impl Tree {
+        fn new_leaf(root: Entity) -> Self {
+            Self {
+                root: Root::Entity(root),
+                branches: Vec::new(),
+            }
+        }
+
+        fn new<T>(root: Entity, branches: impl IntoIterator<Item = T>) -> Tree
+        where
+            T: IntoTree,
+        {
+            Self::with_root(Root::Entity(root), branches)
+        }
+
+        fn with_root<T>(root: Root, branches: impl IntoIterator<Item = T>) -> Tree
+        where
+            T: IntoTree,
+        {
+            // take the root and create branches
+            let branches = branches.into_iter().map(|t| t.into_tree()).collect();
+            Self { root, branches }
+        }
+
+        /// Spawns the bundles and links every branch to its parent, in one pass
+        ///
+        /// Returns the spawned entities by label, see [`BundleNode::label`].
+        pub fn build(self, commands: &mut Commands) -> HashMap<&'static str, Entity> {
+            let mut labels = HashMap::new();
+            self.build_labelled(commands, &mut labels);
+            labels
+        }
+
+        /// Like [`Tree::build`], and also inserts the root into the children of `parent` at `index`,
+        /// e.g. to keep rows sorted
+        pub fn insert(
+            self,
+            commands: &mut Commands,
+            parent: Entity,
+            index: usize,
+        ) -> HashMap<&'static str, Entity> {
+            let mut labels = HashMap::new();
+            let root = self.build_labelled(commands, &mut labels);
+            commands.entity(parent).insert_children(index, &[root]);
+            labels
+        }
+
+        fn build_labelled(
+            self,
+            commands: &mut Commands,
+            labels: &mut HashMap<&'static str, Entity>,
+        ) -> Entity {
+            let id = match self.root {
+                Root::Entity(id) => id,
+                Root::Bundle {
+                    spawn,
+                    always,
+                    label,
+                } => {
+                    let mut entity = commands.spawn_empty();
+                    if let Some(label) = label {
+                        entity.insert(Key(label));
+                        labels.insert(label, entity.id());
+                    }
+                    for apply in std::iter::once(spawn).chain(always) {
+                        apply(&mut entity);
+                    }
+                    entity.id()
+                }
+            };
+            let children = self
+                .branches
+                .into_iter()
+                .map(|branch| branch.build_labelled(commands, labels))
+                .collect::<Vec<_>>();
+            if !children.is_empty() {
+                commands.entity(id).push_children(&children);
+            }
+            id
+        }
+
+        /// What identifies the root among its siblings, see [`reconcile`]
+        fn key(&self) -> Option<&'static str> {
+            match self.root {
+                Root::Entity(_) => None,
+                Root::Bundle { label, .. } => label,
+            }
+        }
+    }
+
+    /// Describe an entity by its bundle, it is spawned when the tree it is part of is built
+    ///
+    /// ```rs
+    /// let labels = node(NodeBundle::default())
+    ///     .label("app_main")
+    ///     .tree((
+    ///         node(TextBundle::from_section("todos", title_style)),
+    ///         node(ButtonBundle::default()).tree(node(TextBundle::from_section("x", style))),
+    ///     ))
+    ///     .build(&mut commands);
+    /// commands.entity(parent).add_child(labels["app_main"]);
+    /// ```
+    pub fn node<B: Bundle>(bundle: B) -> BundleNode<B> {
+        BundleNode {
+            bundle,
+            always: Vec::new(),
+            label: None,
+        }
+    }
+
+    /// See [`node`]
+    pub struct BundleNode<B: Bundle> {
+        bundle: B,
+        always: Vec<Apply>,
+        label: Option<&'static str>,
+    }
+
+    impl<B: Bundle> BundleNode<B> {
+        /// Names the spawned entity in what [`Tree::build`] returns,
+        /// and identifies it among its siblings for [`reconcile`]
+        pub fn label(mut self, label: &'static str) -> Self {
+            self.label = Some(label);
+            self
+        }
+
+        /// `bundle` is inserted whether the entity is spawned or reused by [`reconcile`]
+        pub fn insert(mut self, bundle: impl Bundle) -> Self {
+            self.always
+                .push(Box::new(move |entity: &mut EntityCommands| {
+                    entity.insert(bundle);
+                }));
+            self
+        }
+
+        /// `R` is removed from an entity reused by [`reconcile`]
+        pub fn remove<R: Bundle>(mut self) -> Self {
+            self.always.push(Box::new(|entity: &mut EntityCommands| {
+                entity.remove::<R>();
+            }));
+            self
+        }
+
+        /// Construct a [`Tree`] with this bundle as the root
+        pub fn tree<T, S, I>(self, children: T) -> Tree
+        where
+            T: IntoTreeIterator<IterableStorage = S>,
+            S: IntoIterator<IntoIter = I>,
+            I: Iterator<Item = Tree>,
+        {
+            Tree::with_root(self.into_root(), children.into_tree_iter())
+        }
+
+        fn into_root(self) -> Root {
+            let bundle = self.bundle;
+            Root::Bundle {
+                spawn: Box::new(move |entity: &mut EntityCommands| {
+                    entity.insert(bundle);
+                }),
+                always: self.always,
+                label: self.label,
+            }
+        }
+    }
+
+    /// A type that stores an iterator over trees
+    pub struct TreeIterator<I>
+    where
+        I: Iterator<Item = Tree>,
+    {
+        iter: I,
+    }
+
+    impl<I> TreeIterator<I>
+    where
+        I: Iterator<Item = Tree>,
+    {
+        fn new<S>(iterable: S) -> Self
+        where
+            S: IntoIterator<IntoIter = I>,
+        {
+            let iter = iterable.into_iter();
+            Self { iter }
+        }
+    }
+
+    impl<S, I> From<S> for TreeIterator<I>
+    where
+        S: IntoIterator<IntoIter = I>,
+        I: Iterator<Item = Tree>,
+    {
+        fn from(iterable: S) -> Self {
+            Self::new(iterable)
+        }
+    }
+    trait Identity {
+        type This;
+        fn identity(self) -> Self::This;
+    }
+
+    impl<A> Identity for A {
+        type This = A;
+        fn identity(self) -> Self::This {
+            self
+        }
+    }
+
+    impl<I> Iterator for TreeIterator<I>
+    where
+        I: Iterator<Item = Tree>,
+    {
+        type Item = Tree;
+
+        fn next(&mut self) -> Option<Self::Item> {
+            self.iter.next()
+        }
+    }
+    pub trait EntityTreeExt {
+        fn tree<T, S, I>(self, children: T) -> Tree
+        where
+            // T is the thing that becomes an iterator over `Tree`s, e.g. `(Entity, Entity)`
+            T: IntoTreeIterator<IterableStorage = S>,
+            // S is the iterable storage for the iterator, which becomes an iterator over `Tree`s, e.g. `[Entity, Entity]`
+            S: IntoIterator<IntoIter = I>,
+            // I is the iterator over `Tree`s
+            I: Iterator<Item = Tree>;
+
+        fn l(self) -> Tree;
+    }
+
+    impl EntityTreeExt for Entity {
+        /// Construct a [`Tree`] of entities
+        ///
+        /// # Example
+        ///
+        /// ```rs
+        /// # use bevy::prelude::*;
+        /// # let world = World::new();
+        /// # let mut queue = bevy::ecs::system::CommandQueue::default();
+        /// # let mut commands = Commands::new(&mut queue, &world);
+        /// # let app_main = Entity::PLACEHOLDER;
+        /// # let app_title = Entity::PLACEHOLDER;
+        /// # let todo_main = Entity::PLACEHOLDER;
+        /// # let todo_input_container = Entity::PLACEHOLDER;
+        /// # let todo_list = Entity::PLACEHOLDER;
+        /// # let todo_footer = Entity::PLACEHOLDER;
+        /// # let todo_items_left = Entity::PLACEHOLDER;
+        /// # let todo_filters = Entity::PLACEHOLDER;
+        /// # let todo_filter_all_btn = Entity::PLACEHOLDER;
+        /// # let todo_filter_all_txt = Entity::PLACEHOLDER;
+        /// # let todo_filter_active_btn = Entity::PLACEHOLDER;
+        /// # let todo_filter_active_txt = Entity::PLACEHOLDER;
+        /// # let todo_filter_completed_btn = Entity::PLACEHOLDER;
+        /// # let todo_filter_completed_txt = Entity::PLACEHOLDER;
+        /// # let todo_clear_completed_btn = Entity::PLACEHOLDER;
+        /// # let todo_clear_completed_txt = Entity::PLACEHOLDER;
+        /// // app_main
+        /// // - app_title
+        /// // - todo_main
+        /// //   - todo_input_container
+        /// //   - todo_list
+        /// //   - todo_footer
+        /// //     - todo_items_left
+        /// //     - todo_filters
+        /// //     - todo_filter_all_btn
+        /// //       - todo_filter_all_txt
+        /// //     - todo_filter_active_btn
+        /// //       - todo_filter_active_txt
+        /// //     - todo_filter_completed_btn
+        /// //       - todo_filter_completed_txt
+        /// //     - todo_clear_completed_btn
+        /// //       - todo_clear_completed_txt
+        /// app_main.t((
+        ///     app_title,
+        ///     todo_main.t((
+        ///         todo_input_container,
+        ///         todo_list,
+        ///         todo_footer.t((
+        ///             todo_items_left,
+        ///             todo_filters,
+        ///             todo_filter_all_btn.t(todo_filter_all_txt),
+        ///             todo_filter_active_btn.t(todo_filter_active_txt),
+        ///             todo_filter_completed_btn.t(todo_filter_completed_txt),
+        ///             todo_clear_completed_btn.t(todo_clear_completed_txt),
+        ///         )),
+        ///     )),
+        /// ))
+        /// .build(&mut commands);
+        /// ```
+        fn tree<T, S, I>(self, children: T) -> Tree
+        where
+            T: IntoTreeIterator<IterableStorage = S>,
+            S: IntoIterator<IntoIter = I>,
+            I: Iterator<Item = Tree>,
+        {
+            build_tree(self, children)
+        }
+
+        fn l(self) -> Tree {
+            Tree::new_leaf(self)
+        }
+    }
+
+    pub trait IntoTree {
+        fn into_tree(self) -> Tree;
+    }
+
+    impl IntoTree for Tree {
+        fn into_tree(self) -> Tree {
+            self
+        }
+    }
+
+    impl IntoTree for Entity {
+        fn into_tree(self) -> Tree {
+            Tree::new_leaf(self)
+        }
+    }
+
+    impl<B: Bundle> IntoTree for BundleNode<B> {
+        fn into_tree(self) -> Tree {
+            Tree::with_root(self.into_root(), Vec::<Tree>::new())
+        }
+    }
+
+    pub trait IntoTreeIterator {
+        /// A storage type that can be converted into an iterator over `Tree`s
+        type IterableStorage;
+        fn into_tree_iter<I>(self) -> TreeIterator<I>
+        where
+            Self::IterableStorage: IntoIterator<IntoIter = I>,
+            I: Iterator<Item = Tree>;
+    }
+
+    impl<S: Iterator<Item = Tree>> IntoTreeIterator for TreeIterator<S> {
+        type IterableStorage = Self;
+        fn into_tree_iter<I>(self) -> TreeIterator<I>
+        where
+            Self::IterableStorage: IntoIterator<IntoIter = I>,
+            I: Iterator<Item = Tree>,
+        {
+            self.into()
+        }
+    }
+
+    impl IntoTreeIterator for Entity {
+        type IterableStorage = [Tree; 1];
+        fn into_tree_iter<I>(self) -> TreeIterator<I>
+        where
+            Self::IterableStorage: IntoIterator<IntoIter = I>,
+            I: Iterator<Item = Tree>,
+        {
+            TreeIterator::new([self.into_tree()])
+        }
+    }
+
+    impl<B: Bundle> IntoTreeIterator for BundleNode<B> {
+        type IterableStorage = [Tree; 1];
+        fn into_tree_iter<I>(self) -> TreeIterator<I>
+        where
+            Self::IterableStorage: IntoIterator<IntoIter = I>,
+            I: Iterator<Item = Tree>,
+        {
+            TreeIterator::new([self.into_tree()])
+        }
+    }
+
+    impl<T: IntoTree> IntoTreeIterator for Vec<T> {
+        type IterableStorage = Vec<Tree>;
+        fn into_tree_iter<I>(self) -> TreeIterator<I>
+        where
+            Self::IterableStorage: IntoIterator<IntoIter = I>,
+            I: Iterator<Item = Tree>,
+        {
+            TreeIterator::new(
+                self.into_iter()
+                    .map(IntoTree::into_tree)
+                    .collect::<Vec<_>>(),
+            )
+        }
+    }
+
+    impl IntoTreeIterator for Tree {
+        type IterableStorage = [Tree; 1];
+        fn into_tree_iter<I>(self) -> TreeIterator<I>
+        where
+            Self::IterableStorage: IntoIterator<IntoIter = I>,
+            I: Iterator<Item = Tree>,
+        {
+            TreeIterator::new([self])
+        }
+    }
+
+    // impl_intotreeiter!((T0, t0), (T1, t1));
+    macro_rules! impl_intotreeiter {
+        ($(($T:ident, $t:ident)),*) => {
+            // impl <T0, T1> IntoTreeIterator for (T0, T1) where T0: IntoTree, T1: IntoTree {
+            impl<$($T),*> IntoTreeIterator for ($($T,)*) where $($T: IntoTree),* {
+                // type IterableStorage = [Tree; 0usize + 1usize + 1usize];
+                type IterableStorage = [Tree; 0usize $(+ replace_expr!($T 1usize))*];
+                fn into_tree_iter<I>(self) -> TreeIterator<I>
+                where
+                    Self::IterableStorage: IntoIterator<IntoIter = I>,
+                    I: Iterator<Item = Tree>,
+                {
+                    // let (t0, t1) = self;
+                    let ($($t,)*) = self;
+                    // let source: Self::IterableStorage = [t0.into_tree(), t1.into_tree()];
+                    let source: Self::IterableStorage = [$($t.into_tree()),*];
+                    TreeIterator::new(source)
+                }
+            }
+        }
+    }
+
+    /// Discards the actual tokentree provided (tt), and replaces it with the expression `sub`
+    ///
+    /// See https://veykril.github.io/tlborm/decl-macros/patterns/repetition-replacement.html?highlight=replace_expr#repetition-replacement
+    macro_rules! replace_expr {
+        ($_t:tt $sub:expr) => {
+            $sub
+        };
+    }
+
+    bevy::utils::all_tuples!(impl_intotreeiter, 0, 16, T, t);
+
+    // impl_intotreeiter_for_iter!(<S, F> std::iter::Map<S, F>);
+    macro_rules! impl_intotreeiter_for_iter {
+        ($(<$($T:ident),*> $iter:ty),* $(,)?) => {
+            $(
+                // impl<S, F> IntoTreeIterator for Map<S, F> where Map<S, F>: Iterator, ...
+                impl<$($T),*> IntoTreeIterator for $iter
+                where
+                    $iter: Iterator,
+                    <$iter as Iterator>::Item: IntoTree,
+                {
+                    type IterableStorage = Vec<Tree>;
+                    fn into_tree_iter<I>(self) -> TreeIterator<I>
+                    where
+                        Self::IterableStorage: IntoIterator<IntoIter = I>,
+                        I: Iterator<Item = Tree>,
+                    {
+                        TreeIterator::new(self.map(IntoTree::into_tree).collect::<Vec<_>>())
+                    }
+                }
+            )*
+        };
+    }
+
+    // a blanket impl for every `Iterator` would overlap with the ones above
+    impl_intotreeiter_for_iter!(
+        <S, F> std::iter::Map<S, F>,
+        <S, F> std::iter::FilterMap<S, F>,
+        <S, P> std::iter::Filter<S, P>,
+        <A, B> std::iter::Chain<A, B>,
+        <S> std::iter::Rev<S>,
+        <S> std::iter::Take<S>,
+        <S> std::iter::Skip<S>,
+        <T> std::iter::Once<T>,
+        <T> std::vec::IntoIter<T>,
+        <T> std::option::IntoIter<T>,
+    );
+
+    /// Identifies a child among its siblings, inserted for labelled bundles, see [`reconcile`]
+    #[derive(Component, Clone, Copy, PartialEq, Eq, Hash, Debug)]
+    pub struct Key(pub &'static str);
+
+    /// The `Key` and `Children` of every entity, to find what [`reconcile`] can reuse
+    pub type Keyed<'w, 's> = Query<'w, 's, (Option<&'static Key>, Option<&'static Children>)>;
+
+    /// Builds `trees` without a parent, e.g. the roots described by [`ui!`](crate::ui)
+    ///
+    /// Returns the spawned entities by label, like [`Tree::build`].
+    pub fn spawn(commands: &mut Commands, trees: Vec<Tree>) -> HashMap<&'static str, Entity> {
+        let mut labels = HashMap::new();
+        for tree in trees {
+            tree.build_labelled(commands, &mut labels);
+        }
+        labels
+    }
+
+    /// Makes the labelled children of `parent` match `trees`, recursively:
+    /// children with a matching label are reused, missing ones are spawned,
+    /// and ones that are no longer described are despawned with their descendants.
+    /// Children without a [`Key`] are left alone,
+    /// and trees without a label are built every time.
+    ///
+    /// ```rs
+    /// // checkmark, text, deleter <-> text
+    /// let row = if editing {
+    ///     ui![text: text_view]
+    /// } else {
+    ///     ui![checkmark: checkmark_view, text: text_view, deleter: deleter_view]
+    /// };
+    /// let children = reconcile(&mut commands, &keyed, root, row);
+    /// set_focus.send(SetFocus(children.get("text").copied()));
+    /// ```
+    ///
+    /// Returns the direct children by label.
+    pub fn reconcile(
+        commands: &mut Commands,
+        keyed: &Keyed,
+        parent: Entity,
+        trees: Vec<Tree>,
+    ) -> HashMap<&'static str, Entity> {
+        let mut existing = HashMap::new();
+        if let Ok((_, Some(children))) = keyed.get(parent) {
+            for child in children.iter() {
+                if let Ok((Some(key), _)) = keyed.get(*child) {
+                    existing.insert(key.0, *child);
+                }
+            }
+        }
+
+        let mut reconciled = HashMap::new();
+        let mut ordered = Vec::with_capacity(trees.len());
+        for tree in trees {
+            let key = tree.key();
+            let entity = match key.and_then(|key| existing.remove(key)) {
+                Some(entity) => {
+                    if let Root::Bundle { always, .. } = tree.root {
+                        let mut entity_commands = commands.entity(entity);
+                        for apply in always {
+                            apply(&mut entity_commands);
+                        }
+                    }
+                    reconcile(commands, keyed, entity, tree.branches);
+                    entity
+                }
+                None => tree.build_labelled(commands, &mut HashMap::new()),
+            };
+            if let Some(key) = key {
+                reconciled.insert(key, entity);
+            }
+            ordered.push(entity);
+        }
+
+        for (_, removed) in existing {
+            commands.entity(removed).despawn_recursive();
+        }
+        // moves reused children into place, after the unkeyed ones
+        commands.entity(parent).push_children(&ordered);
+        reconciled
+    }
+
+    /// Describe trees of labelled [`node`]s, for [`spawn`] or [`reconcile`]
+    ///
+    /// ```rs
+    /// ui![
+    ///     // key: bundle => [children]
+    ///     todo_check_btn: (ButtonBundle::default(), View(model)) => [
+    ///         todo_check_txt: TextBundle::from_section("✓", style),
+    ///     ],
+    ///     // key: bundle; node methods, applied whether the child is spawned or reused
+    ///     todo_text_btn: (CosmicEditUiBundle::default(), View(model)); insert(ReadOnly),
+    ///     // key: bundle => children from anything `tree` takes, e.g. another `ui!`
+    ///     todo_delete_btn: ButtonBundle::default() => delete_icon(),
+    /// ]
+    /// ```
+    ///
+    /// Expands to a `Vec<Tree>`, the key is the label of each [`BundleNode`].
+    #[macro_export]
+    macro_rules! ui {
+        (@list [$($out:expr),*]) => {
+            vec![$($out),*]
+        };
+        (@list [$($out:expr),*]
+            $key:ident : $bundle:expr $(; $method:ident $(::<$ty:ty>)? ($($arg:expr)?))*
+            => [$($children:tt)*] $(, $($rest:tt)*)?
+        ) => {
+            $crate::ui!(@list [$($out,)* $crate::tree_builder::node($bundle)
+                .label(stringify!($key))
+                $(.$method$(::<$ty>)?($($arg)?))*
+                .tree($crate::ui!(@list [] $($children)*))
+            ] $($($rest)*)?)
+        };
+        (@list [$($out:expr),*]
+            $key:ident : $bundle:expr $(; $method:ident $(::<$ty:ty>)? ($($arg:expr)?))*
+            => $children:expr $(, $($rest:tt)*)?
+        ) => {
+            $crate::ui!(@list [$($out,)* $crate::tree_builder::node($bundle)
+                .label(stringify!($key))
+                $(.$method$(::<$ty>)?($($arg)?))*
+                .tree($children)
+            ] $($($rest)*)?)
+        };
+        (@list [$($out:expr),*]
+            $key:ident : $bundle:expr $(; $method:ident $(::<$ty:ty>)? ($($arg:expr)?))*
+            $(, $($rest:tt)*)?
+        ) => {
+            $crate::ui!(@list [$($out,)* $crate::tree_builder::IntoTree::into_tree(
+                $crate::tree_builder::node($bundle)
+                    .label(stringify!($key))
+                    $(.$method$(::<$ty>)?($($arg)?))*
+            )] $($($rest)*)?)
+        };
+        ($($nodes:tt)*) => {
+            $crate::ui!(@list [] $($nodes)*)
+        };
+    }
+}
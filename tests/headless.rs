@@ -0,0 +1,762 @@
+//! Drives [`TodoMvcCorePlugin`] with `MinimalPlugins`, no window, GPU or assets needed
+//!
+//! Input is injected the way the library-provided systems would produce it:
+//! `Interaction` on views, `Input<KeyCode>` presses and cosmic-edit's `CosmicTextChanged`.
+
+use std::time::Duration;
+
+use bevy::{input::keyboard::KeyboardInput, prelude::*};
+use bevy_cosmic_edit::{CosmicAttrs, CosmicTextChanged, Focus};
+use bevy_todomvc::{
+    bevy_color_to_cosmic, markers, ClickTracker, DerivedModel, ModelActionError,
+    ModelActionErrorReason, ModelInputText, ModelOnly, ModelTodoAction, ModelTodoChecked,
+    ModelTodoEdit, ModelTodoText, Recording, Theme, TodoActiveCount, TodoAllChecked,
+    TodoCompletedCount, TodoId, TodoMvcCorePlugin, TodoMvcFeatures, TodoMvcRecording,
+    TodoTotalCount, View, ViewOnly,
+};
+
+struct Harness {
+    app: App,
+    frames: usize,
+}
+
+impl Harness {
+    fn new() -> Self {
+        Self::with_recording(TodoMvcRecording::Off)
+    }
+
+    fn with_recording(recording: TodoMvcRecording) -> Self {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .init_resource::<Input<KeyCode>>()
+            .add_plugins(TodoMvcCorePlugin {
+                parent: None,
+                features: TodoMvcFeatures {
+                    persistence: false,
+                    routing: false,
+                    ..default()
+                },
+                recording,
+            });
+        let mut harness = Self { app, frames: 0 };
+        harness.settle();
+        harness
+    }
+
+    fn update(&mut self) {
+        self.app.update();
+        self.frames += 1;
+    }
+
+    /// Enough frames for events, deferred commands and `SetFocus` to go through
+    fn settle(&mut self) {
+        for _ in 0..3 {
+            self.update();
+        }
+    }
+
+    fn press(&mut self, key: KeyCode) {
+        self.press_with(&[], key);
+    }
+
+    fn press_with(&mut self, modifiers: &[KeyCode], key: KeyCode) {
+        let mut keys = self.app.world.resource_mut::<Input<KeyCode>>();
+        for modifier in modifiers {
+            keys.press(*modifier);
+        }
+        keys.press(key);
+        self.update();
+        let mut keys = self.app.world.resource_mut::<Input<KeyCode>>();
+        keys.reset_all();
+        self.settle();
+    }
+
+    /// The editor's whole text changed, as after a keystroke
+    fn type_text(&mut self, view: Entity, text: &str) {
+        self.app
+            .world
+            .send_event(CosmicTextChanged((view, text.to_string())));
+        self.settle();
+    }
+
+    /// Presses and releases, the view may be gone (e.g. deleted or re-spawned) by the release
+    fn press_interaction(&mut self, view: Entity) {
+        self.app.world.entity_mut(view).insert(Interaction::Pressed);
+        self.update();
+        if let Some(mut view) = self.app.world.get_entity_mut(view) {
+            view.insert(Interaction::None);
+        }
+        self.update();
+    }
+
+    fn click(&mut self, view: Entity) {
+        self.press_interaction(view);
+        self.settle();
+    }
+
+    fn double_click(&mut self, view: Entity) {
+        self.press_interaction(view);
+        self.press_interaction(view);
+        self.settle();
+    }
+
+    fn add_todo(&mut self, text: &str) -> TodoId {
+        let input = self.input_view();
+        self.type_text(input, text);
+        self.press(KeyCode::Return);
+        self.id(text.trim())
+    }
+
+    fn focus(&self) -> Option<Entity> {
+        self.app.world.resource::<Focus>().0
+    }
+
+    fn input_view(&mut self) -> Entity {
+        self.app
+            .world
+            .query_filtered::<Entity, (With<markers::TodoInput>, ViewOnly)>()
+            .single(&self.app.world)
+    }
+
+    fn input_text(&mut self) -> String {
+        self.app
+            .world
+            .query_filtered::<&ModelInputText, ModelOnly>()
+            .single(&self.app.world)
+            .0
+            .clone()
+    }
+
+    /// `(text, checked)` of every todo, in creation order
+    fn todos(&mut self) -> Vec<(String, bool)> {
+        let mut todos = self
+            .app
+            .world
+            .query_filtered::<(&TodoId, &ModelTodoText, &ModelTodoChecked), ModelOnly>()
+            .iter(&self.app.world)
+            .map(|(id, text, checked)| (*id, text.0.clone(), checked.0))
+            .collect::<Vec<_>>();
+        todos.sort_by_key(|(id, ..)| *id);
+        todos
+            .into_iter()
+            .map(|(_, text, checked)| (text, checked))
+            .collect()
+    }
+
+    /// The ids of the displayed rows, in order
+    fn rows(&mut self) -> Vec<TodoId> {
+        let rows = self
+            .app
+            .world
+            .query_filtered::<&Children, With<markers::TodoList>>()
+            .single(&self.app.world)
+            .to_vec();
+        rows.iter()
+            .filter_map(|row| self.app.world.get::<TodoId>(*row).copied())
+            .collect()
+    }
+
+    fn id(&mut self, text: &str) -> TodoId {
+        self.app
+            .world
+            .query_filtered::<(&TodoId, &ModelTodoText), ModelOnly>()
+            .iter(&self.app.world)
+            .find(|(_, todo_text)| todo_text.0 == text)
+            .map(|(id, _)| *id)
+            .unwrap_or_else(|| panic!("no todo {text:?}"))
+    }
+
+    fn is_editing(&mut self, id: TodoId) -> bool {
+        self.app
+            .world
+            .query_filtered::<(&TodoId, &ModelTodoEdit), ModelOnly>()
+            .iter(&self.app.world)
+            .any(|(todo_id, edit)| *todo_id == id && edit.is_editing())
+    }
+
+    /// The interactive view of todo `id` carrying marker `M`
+    fn view<M: Component>(&mut self, id: TodoId) -> Entity {
+        self.app
+            .world
+            .query_filtered::<(Entity, &TodoId), (With<M>, With<Interaction>, ViewOnly)>()
+            .iter(&self.app.world)
+            .find(|(_, view_id)| **view_id == id)
+            .map(|(e, _)| e)
+            .unwrap_or_else(|| panic!("no view of todo {id:?}"))
+    }
+
+    /// The view marked with `M` that isn't part of a todo, e.g. the toggle-all button
+    fn single_view<M: Component>(&mut self) -> Entity {
+        self.app
+            .world
+            .query_filtered::<Entity, (With<M>, With<Interaction>, ViewOnly)>()
+            .single(&self.app.world)
+    }
+
+    fn background(&self, view: Entity) -> Color {
+        self.app.world.get::<BackgroundColor>(view).unwrap().0
+    }
+
+    fn row_border(&mut self, id: TodoId) -> Color {
+        let row = self.view::<markers::TodoRootView>(id);
+        self.app.world.get::<BorderColor>(row).unwrap().0
+    }
+
+    fn checkmark_color(&mut self, id: TodoId) -> Color {
+        self.app
+            .world
+            .query_filtered::<(&Text, &TodoId), (With<markers::TodoCheckmarkView>, ViewOnly)>()
+            .iter(&self.app.world)
+            .find(|(_, view_id)| **view_id == id)
+            .map(|(text, _)| text.sections[0].style.color)
+            .unwrap_or_else(|| panic!("no checkmark of todo {id:?}"))
+    }
+
+    fn count_views<M: Component>(&mut self) -> usize {
+        self.app
+            .world
+            .query_filtered::<(), (With<M>, With<View>)>()
+            .iter(&self.app.world)
+            .count()
+    }
+
+    fn active_count(&mut self) -> usize {
+        self.app
+            .world
+            .query::<&DerivedModel<TodoActiveCount>>()
+            .single(&self.app.world)
+            .0
+             .0
+    }
+
+    fn total_count(&mut self) -> usize {
+        self.app
+            .world
+            .query::<&DerivedModel<TodoTotalCount>>()
+            .single(&self.app.world)
+            .0
+             .0
+    }
+
+    fn completed_count(&mut self) -> usize {
+        self.app
+            .world
+            .query::<&DerivedModel<TodoCompletedCount>>()
+            .single(&self.app.world)
+            .0
+             .0
+    }
+
+    fn all_checked(&mut self) -> bool {
+        self.app
+            .world
+            .query::<&DerivedModel<TodoAllChecked>>()
+            .single(&self.app.world)
+            .0
+             .0
+    }
+}
+
+#[test]
+fn starts_empty_with_the_input_focused() {
+    let mut harness = Harness::new();
+
+    assert_eq!(harness.todos(), vec![]);
+    assert_eq!(harness.count_views::<markers::TodoRootView>(), 0);
+    assert_eq!(harness.focus(), Some(harness.input_view()));
+}
+
+#[test]
+fn create() {
+    let mut harness = Harness::new();
+
+    harness.add_todo("Buy milk");
+    harness.add_todo("  Walk the dog ");
+
+    assert_eq!(
+        harness.todos(),
+        vec![
+            ("Buy milk".to_string(), false),
+            ("Walk the dog".to_string(), false)
+        ]
+    );
+    assert_eq!(harness.input_text(), "");
+    assert_eq!(harness.active_count(), 2);
+    assert_eq!(harness.total_count(), 2);
+    assert_eq!(harness.count_views::<markers::TodoRootView>(), 2);
+    assert_eq!(harness.focus(), Some(harness.input_view()));
+}
+
+#[test]
+fn create_ignores_blank_text() {
+    let mut harness = Harness::new();
+
+    let input = harness.input_view();
+    harness.type_text(input, "   ");
+    harness.press(KeyCode::Return);
+
+    assert_eq!(harness.todos(), vec![]);
+    assert_eq!(harness.total_count(), 0);
+}
+
+#[test]
+fn check() {
+    let mut harness = Harness::new();
+    let milk = harness.add_todo("Buy milk");
+    harness.add_todo("Walk the dog");
+
+    let checkmark = harness.view::<markers::TodoCheckmarkView>(milk);
+    harness.click(checkmark);
+
+    assert_eq!(
+        harness.todos(),
+        vec![
+            ("Buy milk".to_string(), true),
+            ("Walk the dog".to_string(), false)
+        ]
+    );
+    assert_eq!(harness.active_count(), 1);
+
+    let checkmark = harness.view::<markers::TodoCheckmarkView>(milk);
+    harness.click(checkmark);
+
+    assert_eq!(harness.todos()[0], ("Buy milk".to_string(), false));
+    assert_eq!(harness.active_count(), 2);
+}
+
+#[test]
+fn toggle_all_is_hidden_without_todos() {
+    let mut harness = Harness::new();
+    let toggle_all = harness.single_view::<markers::TodoToggleAll>();
+    let visibility = |harness: &Harness| *harness.app.world.get::<Visibility>(toggle_all).unwrap();
+    assert_eq!(visibility(&harness), Visibility::Hidden);
+
+    let milk = harness.add_todo("Buy milk");
+    assert_eq!(visibility(&harness), Visibility::Inherited);
+
+    let deleter = harness.view::<markers::TodoDeleterView>(milk);
+    harness.click(deleter);
+    assert_eq!(visibility(&harness), Visibility::Hidden);
+}
+
+#[test]
+fn derived_counts_follow_toggle_all_and_clear_completed() {
+    let mut harness = Harness::new();
+    assert!(!harness.all_checked());
+    harness.add_todo("Buy milk");
+    harness.add_todo("Walk the dog");
+
+    let toggle_all = harness.single_view::<markers::TodoToggleAll>();
+    harness.click(toggle_all);
+
+    assert!(harness.all_checked());
+    assert_eq!(harness.completed_count(), 2);
+    assert_eq!(harness.active_count(), 0);
+
+    harness.add_todo("Water the plants");
+
+    assert!(!harness.all_checked());
+    assert_eq!(harness.completed_count(), 2);
+    assert_eq!(harness.total_count(), 3);
+
+    let clear_completed = harness.single_view::<markers::TodoClearCompleted>();
+    harness.click(clear_completed);
+
+    assert_eq!(
+        harness.todos(),
+        vec![("Water the plants".to_string(), false)]
+    );
+    assert!(!harness.all_checked());
+    assert_eq!(harness.completed_count(), 0);
+    assert_eq!(harness.active_count(), 1);
+    assert_eq!(harness.total_count(), 1);
+}
+
+#[test]
+fn switching_the_theme_restyles_existing_and_new_todos() {
+    let mut harness = Harness::new();
+    let milk = harness.add_todo("Buy milk");
+    let checkmark = harness.view::<markers::TodoCheckmarkView>(milk);
+    harness.click(checkmark);
+
+    let (light, dark) = (Theme::light(), Theme::dark());
+    let toggle_all = harness.single_view::<markers::TodoToggleAll>();
+    assert_eq!(
+        harness.background(toggle_all),
+        light.palette.todoapp_background
+    );
+    assert_eq!(
+        harness.row_border(milk),
+        light.palette.todo_list_item_border_bottom
+    );
+    assert_eq!(
+        harness.checkmark_color(milk),
+        light.text.checkmark_completed.color
+    );
+
+    harness.app.world.insert_resource(dark.clone());
+    harness.settle();
+
+    assert_eq!(
+        harness.background(toggle_all),
+        dark.palette.todoapp_background
+    );
+    let checkmark = harness.view::<markers::TodoCheckmarkView>(milk);
+    assert_eq!(
+        harness.background(checkmark),
+        dark.palette.todoapp_background
+    );
+    assert_eq!(
+        harness.row_border(milk),
+        dark.palette.todo_list_item_border_bottom
+    );
+    assert_eq!(
+        harness.checkmark_color(milk),
+        dark.text.checkmark_completed.color
+    );
+
+    let dog = harness.add_todo("Walk the dog");
+    assert_eq!(
+        harness.row_border(dog),
+        dark.palette.todo_list_item_border_bottom
+    );
+    assert_eq!(harness.checkmark_color(dog), dark.text.checkmark.color);
+    let deleter = harness.view::<markers::TodoDeleterView>(dog);
+    assert_eq!(harness.background(deleter), dark.palette.todoapp_background);
+}
+
+#[test]
+fn a_completed_todo_keeps_the_normal_text_color_while_edited_across_theme_switches() {
+    let mut harness = Harness::new();
+    let milk = harness.add_todo("Buy milk");
+    let checkmark = harness.view::<markers::TodoCheckmarkView>(milk);
+    harness.click(checkmark);
+    let text = harness.view::<markers::TodoTextView>(milk);
+    harness.double_click(text);
+
+    let dark = Theme::dark();
+    harness.app.world.insert_resource(dark.clone());
+    harness.settle();
+
+    let editor = harness.view::<markers::TodoTextView>(milk);
+    let attrs = harness.app.world.get::<CosmicAttrs>(editor).unwrap();
+    assert_eq!(
+        attrs.0.color_opt,
+        Some(bevy_color_to_cosmic(dark.text.todo.color))
+    );
+}
+
+/// Values from todomvc-app-css, the light theme is read from `assets/themes/light.theme.ron`
+#[test]
+fn light_theme_has_the_todomvc_colors() {
+    let hex = |hex| Color::hex(hex).unwrap();
+    let light = Theme::light();
+
+    assert_eq!(light.palette.body_background, hex("#f5f5f5"));
+    assert_eq!(light.palette.todoapp_background, hex("#fff"));
+    assert_eq!(light.palette.todo_list_item_completed_color, hex("#d9d9d9"));
+    assert_eq!(light.text.todo.color, hex("#4d4d4d"));
+    assert_eq!(light.text.footer.color, hex("#777"));
+    assert_ne!(
+        Theme::dark().palette.body_background,
+        light.palette.body_background
+    );
+}
+
+#[test]
+fn edit_focuses_the_todo_and_commits_on_enter() {
+    let mut harness = Harness::new();
+    let milk = harness.add_todo("Buy milk");
+
+    let text = harness.view::<markers::TodoTextView>(milk);
+    harness.double_click(text);
+
+    assert!(harness.is_editing(milk));
+    let editor = harness.view::<markers::TodoTextView>(milk);
+    assert_eq!(harness.focus(), Some(editor));
+
+    harness.type_text(editor, "Buy oat milk ");
+    harness.press(KeyCode::Return);
+
+    assert!(!harness.is_editing(milk));
+    assert_eq!(harness.todos(), vec![("Buy oat milk".to_string(), false)]);
+}
+
+#[test]
+fn editing_keeps_the_text_view() {
+    let mut harness = Harness::new();
+    let milk = harness.add_todo("Buy milk");
+
+    let text = harness.view::<markers::TodoTextView>(milk);
+    harness.double_click(text);
+
+    assert_eq!(harness.view::<markers::TodoTextView>(milk), text);
+    assert_eq!(harness.count_views::<markers::TodoCheckmarkView>(), 0);
+    assert_eq!(harness.count_views::<markers::TodoDeleterView>(), 0);
+
+    harness.press(KeyCode::Return);
+
+    assert_eq!(harness.view::<markers::TodoTextView>(milk), text);
+    assert_eq!(harness.count_views::<markers::TodoCheckmarkView>(), 2);
+    assert_eq!(harness.count_views::<markers::TodoDeleterView>(), 1);
+}
+
+#[test]
+fn single_click_does_not_edit() {
+    let mut harness = Harness::new();
+    let milk = harness.add_todo("Buy milk");
+
+    let text = harness.view::<markers::TodoTextView>(milk);
+    harness.click(text);
+
+    assert!(!harness.is_editing(milk));
+}
+
+#[test]
+fn click_tracker_counts_quick_presses_per_key() {
+    let mut clicks = ClickTracker::<TodoId>::default();
+    let at = Duration::from_millis;
+
+    clicks.press(TodoId(0), at(0), Some(Vec2::ZERO));
+    clicks.press(TodoId(1), at(100), Some(Vec2::ZERO));
+    clicks.press(TodoId(0), at(200), Some(Vec2::ONE));
+    assert!(clicks.is_double_click(TodoId(0)));
+    assert_eq!(clicks.count(TodoId(1)), 1);
+
+    // too late, and too far
+    clicks.press(TodoId(0), at(1000), Some(Vec2::ONE));
+    clicks.press(TodoId(1), at(1000), Some(Vec2::new(100., 0.)));
+    assert_eq!(clicks.count(TodoId(0)), 1);
+    assert_eq!(clicks.count(TodoId(1)), 1);
+
+    clicks.forget_before(at(2000));
+    assert_eq!(clicks.count(TodoId(0)), 0);
+}
+
+#[test]
+fn escape_cancels_the_edit() {
+    let mut harness = Harness::new();
+    let milk = harness.add_todo("Buy milk");
+
+    let text = harness.view::<markers::TodoTextView>(milk);
+    harness.double_click(text);
+    let editor = harness.view::<markers::TodoTextView>(milk);
+    harness.type_text(editor, "Buy bread");
+    harness.press(KeyCode::Escape);
+
+    assert!(!harness.is_editing(milk));
+    assert_eq!(harness.todos(), vec![("Buy milk".to_string(), false)]);
+}
+
+#[test]
+fn undo_after_a_cancelled_edit_leaves_the_text_unchanged() {
+    let mut harness = Harness::new();
+    let milk = harness.add_todo("Buy milk");
+    let dog = harness.add_todo("Walk the dog");
+    let checkmark = harness.view::<markers::TodoCheckmarkView>(dog);
+    harness.click(checkmark);
+
+    let text = harness.view::<markers::TodoTextView>(milk);
+    harness.double_click(text);
+    let editor = harness.view::<markers::TodoTextView>(milk);
+    harness.type_text(editor, "Buy bread");
+    harness.press(KeyCode::Escape);
+    harness.press_with(&[KeyCode::ControlLeft], KeyCode::Z);
+
+    assert_eq!(
+        harness.todos(),
+        vec![
+            ("Buy milk".to_string(), false),
+            ("Walk the dog".to_string(), false)
+        ]
+    );
+}
+
+#[test]
+fn undo_restores_a_todo_deleted_by_committing_it_blank() {
+    let mut harness = Harness::new();
+    let milk = harness.add_todo("Buy milk");
+
+    let text = harness.view::<markers::TodoTextView>(milk);
+    harness.double_click(text);
+    let editor = harness.view::<markers::TodoTextView>(milk);
+    harness.type_text(editor, "");
+    harness.press(KeyCode::Return);
+    assert!(harness.todos().is_empty());
+
+    harness.press_with(&[KeyCode::ControlLeft], KeyCode::Z);
+
+    assert_eq!(harness.todos(), vec![("Buy milk".to_string(), false)]);
+}
+
+#[test]
+fn delete() {
+    let mut harness = Harness::new();
+    let milk = harness.add_todo("Buy milk");
+    harness.add_todo("Walk the dog");
+
+    let deleter = harness.view::<markers::TodoDeleterView>(milk);
+    harness.click(deleter);
+
+    assert_eq!(harness.todos(), vec![("Walk the dog".to_string(), false)]);
+    assert_eq!(harness.active_count(), 1);
+    assert_eq!(harness.total_count(), 1);
+    assert_eq!(harness.count_views::<markers::TodoRootView>(), 1);
+}
+
+#[test]
+fn an_action_on_an_unknown_todo_is_rejected() {
+    let mut harness = Harness::new();
+    harness.add_todo("Buy milk");
+
+    harness
+        .app
+        .world
+        .send_event(ModelTodoAction::Delete(TodoId(999)));
+    harness.update();
+
+    let errors = harness.app.world.resource::<Events<ModelActionError>>();
+    let reasons = errors
+        .get_reader()
+        .iter(errors)
+        .map(|error| &error.reason)
+        .collect::<Vec<_>>();
+    assert!(matches!(
+        reasons[..],
+        [ModelActionErrorReason::UnknownTodo(TodoId(999))]
+    ));
+    assert_eq!(harness.todos(), vec![("Buy milk".to_string(), false)]);
+}
+
+#[test]
+fn undo_restores_a_deleted_todo() {
+    let mut harness = Harness::new();
+    let milk = harness.add_todo("Buy milk");
+
+    let deleter = harness.view::<markers::TodoDeleterView>(milk);
+    harness.click(deleter);
+    harness.press_with(&[KeyCode::ControlLeft], KeyCode::Z);
+
+    assert_eq!(harness.todos(), vec![("Buy milk".to_string(), false)]);
+    assert_eq!(harness.count_views::<markers::TodoRootView>(), 1);
+}
+
+#[test]
+fn undo_waits_until_the_input_is_left() {
+    let mut harness = Harness::new();
+    let milk = harness.add_todo("Buy milk");
+    let checkmark = harness.view::<markers::TodoCheckmarkView>(milk);
+    harness.click(checkmark);
+    assert_eq!(harness.focus(), Some(harness.input_view()));
+
+    harness.press_with(&[KeyCode::ControlLeft], KeyCode::Z);
+    assert_eq!(harness.todos(), vec![("Buy milk".to_string(), true)]);
+
+    harness.press(KeyCode::Escape);
+    assert_eq!(harness.focus(), None);
+    harness.press_with(&[KeyCode::ControlLeft], KeyCode::Z);
+    assert_eq!(harness.todos(), vec![("Buy milk".to_string(), false)]);
+}
+
+#[test]
+fn undo_displays_a_restored_todo_in_its_place() {
+    let mut harness = Harness::new();
+    let milk = harness.add_todo("Buy milk");
+    let dog = harness.add_todo("Walk the dog");
+    let plants = harness.add_todo("Water the plants");
+
+    let deleter = harness.view::<markers::TodoDeleterView>(dog);
+    harness.click(deleter);
+    assert_eq!(harness.rows(), vec![milk, plants]);
+
+    harness.press_with(&[KeyCode::ControlLeft], KeyCode::Z);
+
+    assert_eq!(harness.rows(), vec![milk, dog, plants]);
+}
+
+#[test]
+fn replay() {
+    let recording = Recording::parse(
+        r#"(todos: [(id: TodoId(0), text: "Buy milk", checked: false)])
+(frame: 1, input: [UpdateText("Walk the dog")])
+(frame: 2, todo: [Create("Walk the dog")], input: [UpdateText("")])
+(frame: 3, todo: [ToggleChecked(TodoId(0))])
+(frame: 4, todo: [Delete(TodoId(1))])
+(frame: 5, history: [Undo])
+"#,
+    )
+    .unwrap();
+    let mut harness = Harness::with_recording(TodoMvcRecording::Replay(recording));
+    harness.settle();
+    harness.settle();
+
+    assert_eq!(
+        harness.todos(),
+        vec![
+            ("Buy milk".to_string(), true),
+            ("Walk the dog".to_string(), false)
+        ]
+    );
+    assert_eq!(harness.input_text(), "");
+    assert_eq!(harness.active_count(), 1);
+    assert_eq!(harness.count_views::<markers::TodoRootView>(), 2);
+}
+
+#[test]
+fn replay_applies_typed_text_once() {
+    let recording = Recording::parse(
+        r#"(todos: [])
+(frame: 5, raw: [Key(scan_code: 30, key_code: Some(A), pressed: true), Key(scan_code: 30, key_code: Some(A), pressed: false)], input: [UpdateText("a")])
+(frame: 6, raw: [Key(scan_code: 28, key_code: Some(Return), pressed: true)], todo: [Create("a")], input: [UpdateText("")])
+"#,
+    )
+    .unwrap();
+    let mut harness = Harness::with_recording(TodoMvcRecording::Replay(recording));
+    harness.app.add_event::<KeyboardInput>();
+    for _ in 0..6 {
+        harness.update();
+        assert!(
+            harness
+                .app
+                .world
+                .resource::<Events<KeyboardInput>>()
+                .is_empty(),
+            "raw input is not replayed"
+        );
+    }
+
+    assert_eq!(harness.todos(), vec![("a".to_string(), false)]);
+    assert_eq!(harness.input_text(), "");
+}
+
+#[test]
+fn a_recording_replays_to_the_same_todos() {
+    let path = std::env::temp_dir().join(format!("bevy-todomvc-{}.ron", std::process::id()));
+    let mut recorded = Harness::with_recording(TodoMvcRecording::Record(path.clone()));
+    let milk = recorded.add_todo("Buy milk");
+    let dog = recorded.add_todo("Walk the dog");
+    let checkmark = recorded.view::<markers::TodoCheckmarkView>(dog);
+    recorded.click(checkmark);
+    let text = recorded.view::<markers::TodoTextView>(milk);
+    recorded.double_click(text);
+    let editor = recorded.view::<markers::TodoTextView>(milk);
+    recorded.type_text(editor, "Buy oat milk");
+    recorded.press(KeyCode::Return);
+
+    let recording = Recording::load(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+    let mut replayed = Harness::with_recording(TodoMvcRecording::Replay(recording));
+    while replayed.frames < recorded.frames {
+        replayed.update();
+    }
+
+    assert_eq!(
+        recorded.todos(),
+        vec![
+            ("Buy oat milk".to_string(), false),
+            ("Walk the dog".to_string(), true)
+        ]
+    );
+    assert_eq!(replayed.todos(), recorded.todos());
+    assert_eq!(replayed.active_count(), recorded.active_count());
+}
@@ -0,0 +1,27 @@
+//! Parses stored todos, no app or storage needed
+
+use bevy_todomvc::{persistence::parse, TodoId};
+
+#[test]
+fn todos_stored_with_ids() {
+    let todos = parse(r#"[{"id":3,"text":"Buy milk","checked":true}]"#);
+
+    assert_eq!(todos.len(), 1);
+    assert_eq!(todos[0].id, Some(TodoId(3)));
+    assert_eq!(todos[0].text, "Buy milk");
+    assert!(todos[0].checked);
+}
+
+#[test]
+fn todos_stored_before_ids_are_kept() {
+    let todos =
+        parse(r#"[{"text":"Buy milk","checked":true},{"text":"Walk the dog","checked":false}]"#);
+
+    assert_eq!(
+        todos
+            .iter()
+            .map(|todo| (todo.id, todo.text.as_str(), todo.checked))
+            .collect::<Vec<_>>(),
+        vec![(None, "Buy milk", true), (None, "Walk the dog", false)]
+    );
+}
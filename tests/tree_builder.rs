@@ -0,0 +1,212 @@
+//! Builds and reconciles trees against a bare `World`, no app or plugins needed
+
+use bevy::{ecs::system::SystemState, prelude::*};
+use bevy_todomvc::{
+    tree_builder::{c, col, l, node, reconcile, spawn, x, EntityTreeExt, Keyed},
+    ui,
+};
+
+#[derive(Component, Debug, PartialEq)]
+struct Tag(&'static str);
+
+/// Runs `f` with `Commands` and then applies them
+fn with_commands<R>(world: &mut World, f: impl FnOnce(&mut Commands, &Keyed) -> R) -> R {
+    let mut state = SystemState::<(Commands, Keyed)>::new(world);
+    let result = {
+        let (mut commands, keyed) = state.get_mut(world);
+        f(&mut commands, &keyed)
+    };
+    state.apply(world);
+    result
+}
+
+fn children(world: &World, parent: Entity) -> Vec<Entity> {
+    world
+        .get::<Children>(parent)
+        .map(|children| children.to_vec())
+        .unwrap_or_default()
+}
+
+fn tags(world: &World, parent: Entity) -> Vec<&'static str> {
+    children(world, parent)
+        .into_iter()
+        .filter_map(|child| world.get::<Tag>(child).map(|tag| tag.0))
+        .collect()
+}
+
+#[test]
+fn entities_are_linked_in_order() {
+    let mut world = World::new();
+    let [root, a, b, b0] = [(); 4].map(|_| world.spawn_empty().id());
+
+    with_commands(&mut world, |commands, _| {
+        root.tree((a, b.tree(b0))).build(commands);
+    });
+
+    assert_eq!(children(&world, root), vec![a, b]);
+    assert_eq!(children(&world, b), vec![b0]);
+}
+
+#[test]
+fn bundles_are_spawned_and_returned_by_label() {
+    let mut world = World::new();
+
+    let spawned = with_commands(&mut world, |commands, _| {
+        node(Tag("root"))
+            .label("root")
+            .tree((
+                node(Tag("a")),
+                node(Tag("b")).label("b").tree(node(Tag("b0"))),
+            ))
+            .build(commands)
+    });
+
+    assert_eq!(spawned.len(), 2);
+    assert_eq!(tags(&world, spawned["root"]), vec!["a", "b"]);
+    assert_eq!(tags(&world, spawned["b"]), vec!["b0"]);
+}
+
+#[test]
+fn more_than_16_siblings_from_a_vec_or_iterators() {
+    const NAMES: [&str; 20] = [
+        "0", "1", "2", "3", "4", "5", "6", "7", "8", "9", "10", "11", "12", "13", "14", "15", "16",
+        "17", "18", "19",
+    ];
+    let mut world = World::new();
+
+    let spawned = with_commands(&mut world, |commands, _| {
+        let mut spawned = node(Tag("vec"))
+            .label("vec")
+            .tree(
+                NAMES
+                    .map(|name| node(Tag(name)))
+                    .into_iter()
+                    .collect::<Vec<_>>(),
+            )
+            .build(commands);
+        spawned.extend(
+            node(Tag("col"))
+                .label("col")
+                .tree(col(NAMES.iter().map(|&name| node(Tag(name)))))
+                .build(commands),
+        );
+        spawned.extend(
+            node(Tag("map"))
+                .label("map")
+                .tree(NAMES.iter().map(|&name| node(Tag(name))))
+                .build(commands),
+        );
+        spawned
+    });
+
+    assert_eq!(tags(&world, spawned["vec"]), NAMES);
+    assert_eq!(tags(&world, spawned["col"]), NAMES);
+    assert_eq!(tags(&world, spawned["map"]), NAMES);
+}
+
+#[test]
+fn helpers_build_the_same_trees_as_tuples() {
+    let mut world = World::new();
+    let [root, a, b, b0] = [(); 4].map(|_| world.spawn_empty().id());
+
+    with_commands(&mut world, |commands, _| {
+        x(root, vec![l(a), b.tree(c((b0,)))]).build(commands);
+    });
+
+    assert_eq!(children(&world, root), vec![a, b]);
+    assert_eq!(children(&world, b), vec![b0]);
+}
+
+#[test]
+fn a_tree_is_inserted_at_a_position() {
+    let mut world = World::new();
+    let [list, first, last] = [(); 3].map(|_| world.spawn_empty().id());
+    with_commands(&mut world, |commands, _| {
+        list.tree((first, last)).build(commands);
+    });
+
+    let spawned = with_commands(&mut world, |commands, _| {
+        node(Tag("middle"))
+            .label("middle")
+            .tree(node(Tag("child")))
+            .insert(commands, list, 1)
+    });
+
+    assert_eq!(children(&world, list), vec![first, spawned["middle"], last]);
+    assert_eq!(tags(&world, spawned["middle"]), vec!["child"]);
+}
+
+#[test]
+fn reconcile_reuses_spawns_and_despawns_by_key() {
+    let mut world = World::new();
+    let root = world.spawn_empty().id();
+    let row = |keys: &[&'static str]| {
+        keys.iter()
+            .map(|&key| {
+                node(Tag(key))
+                    .label(key)
+                    .tree(node(Tag("icon")).label("icon"))
+            })
+            .collect::<Vec<_>>()
+    };
+
+    let before = with_commands(&mut world, |commands, keyed| {
+        reconcile(
+            commands,
+            keyed,
+            root,
+            row(&["checkmark", "text", "deleter"]),
+        )
+    });
+    let icon = children(&world, before["checkmark"]);
+
+    let editing = with_commands(&mut world, |commands, keyed| {
+        reconcile(commands, keyed, root, row(&["text"]))
+    });
+    assert_eq!(editing["text"], before["text"]);
+    assert_eq!(tags(&world, root), vec!["text"]);
+    assert!(world.get_entity(before["checkmark"]).is_none());
+    assert!(world.get_entity(icon[0]).is_none());
+
+    let after = with_commands(&mut world, |commands, keyed| {
+        reconcile(
+            commands,
+            keyed,
+            root,
+            row(&["checkmark", "text", "deleter"]),
+        )
+    });
+    assert_eq!(after["text"], before["text"]);
+    assert_ne!(after["checkmark"], before["checkmark"]);
+    assert_eq!(tags(&world, root), vec!["checkmark", "text", "deleter"]);
+    assert_eq!(tags(&world, after["checkmark"]), vec!["icon"]);
+}
+
+#[test]
+fn ui_describes_nested_keyed_nodes() {
+    let mut world = World::new();
+    let icon = || ui![icon: Tag("icon")];
+
+    let spawned = with_commands(&mut world, |commands, _| {
+        spawn(
+            commands,
+            ui![
+                root: Tag("root") => [
+                    a: Tag("a"); insert(Visibility::Hidden),
+                    b: (Tag("b"), Visibility::Visible); remove::<Visibility>() => icon(),
+                    c: Tag("c") => [
+                        c0: Tag("c0"),
+                    ],
+                ],
+            ],
+        )
+    });
+
+    let root = spawned["root"];
+    assert_eq!(tags(&world, root), vec!["a", "b", "c"]);
+    let [a, b, c] = <[Entity; 3]>::try_from(children(&world, root)).unwrap();
+    assert_eq!(world.get::<Visibility>(a), Some(&Visibility::Hidden));
+    assert_eq!(world.get::<Visibility>(b), None);
+    assert_eq!(tags(&world, b), vec!["icon"]);
+    assert_eq!(tags(&world, c), vec!["c0"]);
+}